@@ -0,0 +1,73 @@
+//! Benchmarks for the parser and the conversion methods.
+//!
+//! Run them with `cargo bench`, or a single group with (for example) `cargo bench --bench conversions -- parse`.
+//! Criterion keeps the previous run's results in `target/criterion`, and reports how much each
+//! benchmark changed against them.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use cfk::batch::{self, InputFormat};
+use cfk::temp::Temp;
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("short", |b| b.iter(|| black_box("32F").parse::<Temp>()));
+    group.bench_function("long decimal", |b| {
+        b.iter(|| black_box("-273.1234567890123456789012345K").parse::<Temp>())
+    });
+    group.bench_function("whitespace", |b| {
+        b.iter(|| black_box("      98.6          F").parse::<Temp>())
+    });
+    group.bench_function("invalid", |b| {
+        b.iter(|| black_box("123sdafsd23445.4F").parse::<Temp>())
+    });
+    group.finish();
+}
+
+fn convert(c: &mut Criterion) {
+    let temps: Vec<(&str, Temp)> = ["37.5C", "98.6F", "310.65K"]
+        .into_iter()
+        .map(|temp_str| {
+            (
+                temp_str,
+                temp_str.parse().expect("benchmark inputs are valid"),
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("convert");
+    for (name, temp) in temps {
+        group.bench_function(format!("{name} to celsius"), |b| {
+            b.iter(|| black_box(temp).to_celsius())
+        });
+        group.bench_function(format!("{name} to fahrenheit"), |b| {
+            b.iter(|| black_box(temp).to_fahrenheit())
+        });
+        group.bench_function(format!("{name} to kelvin"), |b| {
+            b.iter(|| black_box(temp).to_kelvin())
+        });
+    }
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    // A long log of readings from a sensor that only reports a few distinct values
+    let input: String = (0..10_000)
+        .map(|index| format!("{}.{}C\n", 20 + index % 4, index % 5))
+        .collect();
+    let entries = batch::read_entries(InputFormat::Lines, input.as_bytes())
+        .expect("benchmark input is readable");
+    let convert = |temp: Temp| Ok(temp.to_fahrenheit());
+
+    let mut group = c.benchmark_group("batch");
+    group.bench_function("repetitive cached", |b| {
+        b.iter(|| batch::convert_entries(black_box(entries.clone()), str::parse, convert))
+    });
+    group.bench_function("repetitive uncached", |b| {
+        b.iter(|| batch::convert_entries_cached(black_box(entries.clone()), str::parse, convert, 0))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, convert, batch);
+criterion_main!(benches);
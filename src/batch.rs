@@ -0,0 +1,1790 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, Read, Write};
+use std::ops::{ControlFlow, Range};
+use std::path::Path;
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde_json::{json, Value};
+
+use crate::error::{ErrorFormat, TempError};
+use crate::temp::{DisplayOptions, Temp, TempUnit, UnitOrder};
+use crate::template::Template;
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+/// How temperatures are laid out when read from stdin
+pub enum InputFormat {
+    /// One temperature per line
+    #[default]
+    Lines,
+    /// A JSON array of strings
+    Json,
+    /// Comma-separated temperatures, any number per record
+    Csv,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+/// How batch results are written to stdout
+pub enum OutputFormat {
+    /// One result sentence per line, with failures on stderr
+    Lines,
+    /// A JSON array of input/output (or input/error) objects
+    Json,
+    /// One input/output (or input/error) JSON object per line, each written as soon as its value
+    /// is converted (except from JSON input, or with --parallel)
+    Ndjson,
+    /// input,output,error records with a header
+    Csv,
+}
+
+impl From<InputFormat> for OutputFormat {
+    fn from(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Lines => OutputFormat::Lines,
+            InputFormat::Json => OutputFormat::Json,
+            InputFormat::Csv => OutputFormat::Csv,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of converting one batch entry
+pub struct Record {
+    /// Where the entry was found: the line number, or the array position for JSON
+    pub position: usize,
+    pub input: String,
+    /// The original and converted temperatures
+    pub result: Result<(Temp, Temp), TempError>,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+/// How many batch entries converted, and how many didn't
+pub struct Tally {
+    pub converted: usize,
+    pub failed: usize,
+}
+
+impl Tally {
+    pub fn of(records: &[Record]) -> Self {
+        let converted = records
+            .iter()
+            .filter(|record| record.result.is_ok())
+            .count();
+        Tally {
+            converted,
+            failed: records.len() - converted,
+        }
+    }
+
+    /// The counts in a fixed form for scripts and dashboards to read, `ok=<converted>
+    /// err=<failed>` (ex. ok=980 err=20). Unlike `Display`, this won't change.
+    pub fn counts(&self) -> String {
+        format!("ok={} err={}", self.converted, self.failed)
+    }
+}
+
+/// Gives the entries that failed because of their unit (ex. 15d, or 100 with `--require-unit`)
+/// another chance. `pick` is asked for the unit each one is really in, such as by prompting, and
+/// its number is read again in that unit and passed to `convert`. Entries that `pick` gives no unit
+/// for keep their error.
+pub fn fix_units(
+    records: &mut [Record],
+    mut pick: impl FnMut(&str, &TempError) -> Option<TempUnit>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+) {
+    for record in records {
+        let Err(
+            err @ (TempError::InvalidUnit(_)
+            | TempError::InvalidUnitName(_)
+            | TempError::MisspelledUnit(..)
+            | TempError::MissingUnit(_)),
+        ) = &record.result
+        else {
+            continue;
+        };
+        if let Some(unit) = pick(&record.input, err) {
+            let number = record
+                .input
+                .trim()
+                .trim_end_matches(|c: char| !(c.is_ascii_digit() || c == '.'));
+            record.result = Temp::parse_assuming(number, unit)
+                .and_then(|original| Ok((original, convert(original)?)));
+        }
+    }
+}
+
+/// Reads and converts entries like [`read_entries`] and [`convert_entries`] together, but stops
+/// at the first that fails, which is the last record returned. As with [`read_first_entries`],
+/// nothing past the line (or JSON string) holding it is read from `source`, so a pipe that's still
+/// being written isn't waited on. No more than `limit` entries are read, if it's given.
+pub fn convert_until_failure(
+    format: InputFormat,
+    source: impl BufRead,
+    limit: Option<usize>,
+    parse: impl Fn(&str) -> Result<Temp, TempError>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+) -> Result<Vec<Record>, String> {
+    let mut records = Vec::new();
+    let limit = limit.unwrap_or(usize::MAX);
+    if limit == 0 {
+        return Ok(records);
+    }
+    for_each_entry(format, source, |position, input| {
+        let record = convert_entry((position, input), &parse, &convert);
+        let failed = record.result.is_err();
+        records.push(record);
+        match failed || records.len() >= limit {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        }
+    })?;
+    Ok(records)
+}
+
+/// Reads and converts entries like [`convert_until_failure`], but hands each record to `write` as
+/// soon as it's converted rather than keeping them, so a stream that's still being written is
+/// converted as it arrives. No more than `limit` entries are read, if it's given, and with
+/// `fail_fast`, nothing past the first entry that fails. Returns how many of the records converted.
+pub fn convert_streamed(
+    format: InputFormat,
+    source: impl BufRead,
+    limit: Option<usize>,
+    fail_fast: bool,
+    parse: impl Fn(&str) -> Result<Temp, TempError>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+    mut write: impl FnMut(&Record) -> std::io::Result<()>,
+) -> Result<Tally, String> {
+    let mut tally = Tally::default();
+    let limit = limit.unwrap_or(usize::MAX);
+    if limit == 0 {
+        return Ok(tally);
+    }
+    let mut written = Ok(());
+    for_each_entry(format, source, |position, input| {
+        let record = convert_entry((position, input), &parse, &convert);
+        match record.result.is_ok() {
+            true => tally.converted += 1,
+            false => tally.failed += 1,
+        }
+        written = write(&record);
+        let stop = written.is_err()
+            || (fail_fast && record.result.is_err())
+            || tally.converted + tally.failed >= limit;
+        match stop {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        }
+    })?;
+    written.map_err(|err| format!("Unable to write results: {err}"))?;
+    Ok(tally)
+}
+
+/// Where the entries that failed were found, in order and without repeats (a CSV line with
+/// several bad fields is listed once).
+pub fn failed_positions(records: &[Record]) -> Vec<usize> {
+    let mut positions: Vec<usize> = records
+        .iter()
+        .filter(|record| record.result.is_err())
+        .map(|record| record.position)
+        .collect();
+    positions.dedup();
+    positions
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "converted {}, failed {}, total {}",
+            self.converted,
+            self.failed,
+            self.converted + self.failed
+        )
+    }
+}
+
+/// The mark some editors, mostly on Windows, start a text file with
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Drops what a Windows-authored file can leave around a line once it's been split off: a byte
+/// order mark before the first line (at `index` 0), and a carriage return from a CRLF ending.
+pub(crate) fn clean_line(index: usize, line: &str) -> &str {
+    let line = match index {
+        0 => line.strip_prefix(BYTE_ORDER_MARK).unwrap_or(line),
+        _ => line,
+    };
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Splits the batch input into its raw entries, each tagged with its position. Byte order marks
+/// and CRLF line endings are dropped before anything is read.
+pub fn read_entries(
+    format: InputFormat,
+    source: impl BufRead,
+) -> Result<Vec<(usize, String)>, String> {
+    let mut entries = Vec::new();
+    for_each_entry(format, source, |position, entry| {
+        entries.push((position, entry));
+        ControlFlow::Continue(())
+    })?;
+    Ok(entries)
+}
+
+/// Like [`read_entries`], but stops after the first `limit` entries. Nothing past the line (or
+/// JSON string) holding the last of them is read from `source`, so the rest of a pipe is left
+/// alone.
+pub fn read_first_entries(
+    format: InputFormat,
+    source: impl BufRead,
+    limit: usize,
+) -> Result<Vec<(usize, String)>, String> {
+    let mut entries = Vec::new();
+    if limit == 0 {
+        return Ok(entries);
+    }
+    for_each_entry(format, source, |position, entry| {
+        entries.push((position, entry));
+        match entries.len() < limit {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        }
+    })?;
+    Ok(entries)
+}
+
+/// Reads every line of the batch input as it is, each tagged with its line number, for rewriting
+/// in place with [`write_in_place`]. Only a byte order mark and the CR of a CRLF ending are
+/// dropped: lines aren't trimmed, and blank ones are kept.
+pub fn read_lines(source: impl BufRead) -> Result<Vec<(usize, String)>, String> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.map_err(|err| format!("Unable to read line {}: {err}", index + 1))?;
+            Ok((index + 1, clean_line(index, &line).to_string()))
+        })
+        .collect()
+}
+
+/// Hands each entry of the batch input to `each`, tagged with its position, until `each` breaks
+/// or the input runs out.
+fn for_each_entry(
+    format: InputFormat,
+    mut source: impl BufRead,
+    mut each: impl FnMut(usize, String) -> ControlFlow<()>,
+) -> Result<(), String> {
+    if format == InputFormat::Json {
+        // A byte order mark can only come before everything else
+        let mut mark = [0; 4];
+        let mark = BYTE_ORDER_MARK.encode_utf8(&mut mark).as_bytes();
+        if source
+            .fill_buf()
+            .is_ok_and(|buffer| buffer.starts_with(mark))
+        {
+            source.consume(mark.len());
+        }
+        let stopped = Cell::new(false);
+        let mut strings = JsonStrings {
+            each,
+            stopped: &stopped,
+        };
+        let source = UntilStopped {
+            source,
+            stopped: &stopped,
+        };
+        let mut deserializer = serde_json::Deserializer::from_reader(source);
+        let read = deserializer
+            .deserialize_seq(&mut strings)
+            .and_then(|()| deserializer.end());
+        return match read {
+            // Stopping early leaves the rest of the array unread, which isn't a mistake
+            Err(_) if stopped.get() => Ok(()),
+            Err(err) if err.is_io() => Err(format!("Unable to read input: {err}")),
+            read => read.map_err(|err| format!("Expected a JSON array of strings: {err}")),
+        };
+    }
+
+    for (index, line) in source.lines().enumerate() {
+        let line = line.map_err(|err| format!("Unable to read line {}: {err}", index + 1))?;
+        let line = clean_line(index, &line);
+        let fields: Vec<&str> = match format {
+            InputFormat::Csv => line
+                .split(',')
+                .map(|field| field.trim().trim_matches('"'))
+                .collect(),
+            _ => vec![line.trim()],
+        };
+        for field in fields.into_iter().filter(|field| !field.is_empty()) {
+            if each(index + 1, field.to_string()).is_break() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the strings of a JSON array for [`for_each_entry`], each with its position counting
+/// from 1. Anything but an array of strings is an error.
+struct JsonStrings<'a, F> {
+    each: F,
+    /// Set once `each` breaks off before the end of the array
+    stopped: &'a Cell<bool>,
+}
+
+impl<'de, F: FnMut(usize, String) -> ControlFlow<()>> Visitor<'de> for &mut JsonStrings<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("an array of strings")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut position = 0;
+        while let Some(value) = seq.next_element::<String>()? {
+            position += 1;
+            if (self.each)(position, value).is_break() {
+                self.stopped.set(true);
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads from `source` as if it had ended once `stopped` is set, so that nothing after the last
+/// string wanted from a JSON array is waited on.
+struct UntilStopped<'a, R> {
+    source: R,
+    stopped: &'a Cell<bool>,
+}
+
+impl<R: Read> Read for UntilStopped<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.stopped.get() {
+            true => Ok(0),
+            false => self.source.read(buf),
+        }
+    }
+}
+
+/// Wraps batch input so that bytes which aren't valid UTF-8 are read as U+FFFD (�) rather than
+/// failing the whole read. The input is decoded a line at a time, so only the lines holding bad
+/// bytes are changed, and they usually go on to fail on their own.
+pub struct LossyUtf8<R> {
+    source: R,
+    /// The current line, decoded
+    line: Vec<u8>,
+    /// How much of `line` has been read
+    consumed: usize,
+}
+
+impl<R: BufRead> LossyUtf8<R> {
+    pub fn new(source: R) -> Self {
+        LossyUtf8 {
+            source,
+            line: Vec::new(),
+            consumed: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for LossyUtf8<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.fill_buf()?.read(buf)?;
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for LossyUtf8<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.consumed == self.line.len() {
+            let mut raw = Vec::new();
+            self.source.read_until(b'\n', &mut raw)?;
+            // A newline is never part of a longer character, so each line decodes on its own
+            self.line = match String::from_utf8(raw) {
+                Ok(line) => line.into_bytes(),
+                Err(err) => String::from_utf8_lossy(err.as_bytes())
+                    .into_owned()
+                    .into_bytes(),
+            };
+            self.consumed = 0;
+        }
+        Ok(&self.line[self.consumed..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.consumed = (self.consumed + amount).min(self.line.len());
+    }
+}
+
+/// Works out the unit of a file's unit-less values from a trailing `_C`, `_F`, or `_K` in its name
+/// (ex. `sensor_C.csv`). Only the last extension is ignored.
+pub fn unit_from_filename(path: &Path) -> Result<TempUnit, String> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit_once('_'))
+        .and_then(|(_, token)| {
+            let mut chars = token.chars();
+            match (chars.next(), chars.next()) {
+                (Some(unit), None) => TempUnit::try_from(unit).ok(),
+                _ => None,
+            }
+        })
+        .ok_or_else(|| {
+            format!(
+                "{} doesn't end in a unit token like _C, _F, or _K",
+                path.display()
+            )
+        })
+}
+
+/// Reads the unit to convert into from the first line of `source`, leaving the rest of the input
+/// to be read as values.
+pub fn read_unit_line(source: &mut impl BufRead) -> Result<TempUnit, String> {
+    let mut line = String::new();
+    source
+        .read_line(&mut line)
+        .map_err(|err| format!("Unable to read the unit line: {err}"))?;
+    match clean_line(0, &line).trim() {
+        "" => Err("Expected a unit on the first line of input".to_string()),
+        name => TempUnit::parse(name)
+            .map_err(|err| format!("Expected a unit on the first line of input, but {err}")),
+    }
+}
+
+/// How many distinct inputs [`convert_entries`] remembers the results of
+pub const CACHE_CAPACITY: usize = 4096;
+
+/// Reads every entry with `parse` (usually `Temp::from_str`) and converts it, keeping failures
+/// alongside successes. Inputs that repeat are only read and converted the first time, as long
+/// as there have been no more than [`CACHE_CAPACITY`] distinct inputs before them.
+pub fn convert_entries(
+    entries: Vec<(usize, String)>,
+    parse: impl Fn(&str) -> Result<Temp, TempError>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+) -> Vec<Record> {
+    convert_entries_cached(entries, parse, convert, CACHE_CAPACITY)
+}
+
+/// Like [`convert_entries`], but remembers the results of up to `capacity` distinct inputs. Once
+/// that many are remembered, new inputs are still converted, just not remembered, so memory stays
+/// bounded however many distinct values the input has. A capacity of 0 converts every entry.
+pub fn convert_entries_cached(
+    entries: Vec<(usize, String)>,
+    parse: impl Fn(&str) -> Result<Temp, TempError>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+    capacity: usize,
+) -> Vec<Record> {
+    let mut cache: HashMap<String, Result<(Temp, Temp), TempError>> = HashMap::new();
+    entries
+        .into_iter()
+        .map(|(position, input)| {
+            if let Some(result) = cache.get(&input) {
+                return Record {
+                    position,
+                    result: result.clone(),
+                    input,
+                };
+            }
+            let record = convert_entry((position, input), &parse, &convert);
+            if cache.len() < capacity {
+                cache.insert(record.input.clone(), record.result.clone());
+            }
+            record
+        })
+        .collect()
+}
+
+/// Like [`convert_entries`], but spreads the work across threads. Records come back in the same
+/// order as the entries.
+pub fn convert_entries_parallel(
+    entries: Vec<(usize, String)>,
+    parse: impl Fn(&str) -> Result<Temp, TempError> + Sync,
+    convert: impl Fn(Temp) -> Result<Temp, TempError> + Sync,
+) -> Vec<Record> {
+    entries
+        .into_par_iter()
+        .map(|entry| convert_entry(entry, &parse, &convert))
+        .collect()
+}
+
+fn convert_entry(
+    (position, input): (usize, String),
+    parse: impl Fn(&str) -> Result<Temp, TempError>,
+    convert: impl Fn(Temp) -> Result<Temp, TempError>,
+) -> Record {
+    let result = parse(&input).and_then(|original| Ok((original, convert(original)?)));
+    Record {
+        position,
+        input,
+        result,
+    }
+}
+
+/// Writes the results in the given format. In `Lines` format, failures go to `errors` rather
+/// than `out`, described in `error_format`.
+pub fn write_records(
+    format: OutputFormat,
+    records: &[Record],
+    template: &Template,
+    options: &DisplayOptions,
+    error_format: ErrorFormat,
+    mut out: impl Write,
+    errors: impl Write,
+) -> std::io::Result<()> {
+    match format {
+        OutputFormat::Lines => {
+            write_joined(records, template, options, "\n", error_format, out, errors)?
+        }
+        OutputFormat::Json => {
+            let values = records
+                .iter()
+                .map(|record| json_record(record, options))
+                .collect();
+            writeln!(out, "{}", Value::Array(values))?;
+        }
+        OutputFormat::Ndjson => {
+            for record in records {
+                writeln!(out, "{}", json_record(record, options))?;
+            }
+        }
+        OutputFormat::Csv => {
+            writeln!(out, "input,output,error")?;
+            for record in records {
+                let (output, error) = match &record.result {
+                    Ok((_, new_temp)) => (compact_with(*new_temp, options), String::new()),
+                    Err(err) => (String::new(), err.to_string()),
+                };
+                writeln!(
+                    out,
+                    "{},{},{}",
+                    csv_field(&record.input),
+                    csv_field(&output),
+                    csv_field(&error)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes one result as `Lines` format does, with a failure going to `errors` described in
+/// `error_format`, and flushes it so it can be read straight away.
+pub fn write_line_record(
+    record: &Record,
+    template: &Template,
+    options: &DisplayOptions,
+    error_format: ErrorFormat,
+    mut out: impl Write,
+    errors: impl Write,
+) -> std::io::Result<()> {
+    match &record.result {
+        Ok((original, new_temp)) => {
+            writeln!(out, "{}", template.render(*original, *new_temp, options))?;
+            out.flush()
+        }
+        Err(err) => write_failure(record, err, error_format, errors),
+    }
+}
+
+/// Writes one result as a line of `Ndjson` output, and flushes it so it can be read straight away.
+pub fn write_ndjson_record(
+    record: &Record,
+    options: &DisplayOptions,
+    mut out: impl Write,
+) -> std::io::Result<()> {
+    writeln!(out, "{}", json_record(record, options))?;
+    out.flush()
+}
+
+/// Writes the results as `Lines` format does, but with `separator` between them rather than a
+/// newline (ex. 32 F, 212 F), ending with a newline once every result is written. A single result
+/// is written on its own. Failures go to `errors` as usual, described in `error_format`.
+pub fn write_joined(
+    records: &[Record],
+    template: &Template,
+    options: &DisplayOptions,
+    separator: &str,
+    error_format: ErrorFormat,
+    mut out: impl Write,
+    mut errors: impl Write,
+) -> std::io::Result<()> {
+    let mut written = false;
+    for record in records {
+        match &record.result {
+            Ok((original, new_temp)) => {
+                if written {
+                    write!(out, "{separator}")?;
+                }
+                write!(out, "{}", template.render(*original, *new_temp, options))?;
+                written = true;
+            }
+            Err(err) => write_failure(record, err, error_format, &mut errors)?,
+        }
+    }
+    if written {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Writes a whitespace-aligned table back out line by line from `lines` (as [`read_lines`] gives
+/// them), with only the temperature in `column` (counting from 1) replaced by its conversion from
+/// `records`, in the same number-letter form it was read in (ex. 212F). Everything else on the
+/// line, spacing included, is kept. Lines without a record, like blank ones, are written as they
+/// were, and so are lines that failed, such as headers or short rows, whose failures still go to
+/// `errors` as in `Lines` format.
+pub fn write_in_place(
+    lines: &[(usize, String)],
+    records: &[Record],
+    column: usize,
+    options: &DisplayOptions,
+    error_format: ErrorFormat,
+    mut out: impl Write,
+    mut errors: impl Write,
+) -> std::io::Result<()> {
+    let mut records = records.iter().peekable();
+    for (position, line) in lines {
+        let Some(record) = records.next_if(|record| record.position == *position) else {
+            writeln!(out, "{line}")?;
+            continue;
+        };
+        match (&record.result, column_span(line, column)) {
+            (Ok((_, new_temp)), Some(span)) => {
+                let converted = compact_with(*new_temp, options);
+                writeln!(
+                    out,
+                    "{}{converted}{}",
+                    &line[..span.start],
+                    &line[span.end..]
+                )?
+            }
+            (Ok(_), None) => {
+                let err = TempError::MissingColumn(column);
+                write_failure(record, &err, error_format, &mut errors)?;
+                writeln!(out, "{line}")?
+            }
+            (Err(err), _) => {
+                write_failure(record, err, error_format, &mut errors)?;
+                writeln!(out, "{line}")?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Describes a failed entry in `error_format`, with its line in human descriptions.
+fn write_failure(
+    record: &Record,
+    err: &TempError,
+    error_format: ErrorFormat,
+    mut errors: impl Write,
+) -> std::io::Result<()> {
+    match error_format {
+        ErrorFormat::Human => writeln!(
+            errors,
+            "Unable to convert {} on line {}: {err}",
+            record.input, record.position
+        ),
+        ErrorFormat::Json => writeln!(errors, "{}", err.to_json(&record.input)),
+    }
+}
+
+/// The field in `column` (counting from 1) of a line split on whitespace.
+pub fn column(line: &str, column: usize) -> Result<&str, TempError> {
+    column_span(line, column)
+        .map(|span| &line[span])
+        .ok_or(TempError::MissingColumn(column))
+}
+
+/// Where the field in `column` (counting from 1) of a whitespace-split line starts and ends.
+fn column_span(line: &str, column: usize) -> Option<Range<usize>> {
+    let (mut field_start, mut fields) = (None, 0);
+    // The extra space at the end finishes the last field
+    for (index, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (c.is_whitespace(), field_start) {
+            (false, None) => field_start = Some(index),
+            (true, Some(start)) => {
+                fields += 1;
+                if fields == column {
+                    return Some(start..index);
+                }
+                field_start = None;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Answers a JSON array of `{"value": "100C", "to": "F"}` requests with a JSON array of results,
+/// in the same order. Each item converts or fails on its own, so only input that isn't an array
+/// at all is an error.
+pub fn convert_json_requests(mut source: impl BufRead) -> Result<Value, String> {
+    let mut text = String::new();
+    source
+        .read_to_string(&mut text)
+        .map_err(|err| format!("Unable to read input: {err}"))?;
+    let requests: Vec<Value> = serde_json::from_str(&text)
+        .map_err(|err| format!("Expected a JSON array of requests: {err}"))?;
+    Ok(requests.iter().map(json_response).collect())
+}
+
+/// Answers newline-delimited JSON requests like `{"value": "100C", "to": "F"}` one line at a
+/// time, writing a result for each to `out` as soon as it's read, so a long-running stream is
+/// converted as it arrives. A line that isn't JSON gets an error object with its line number in
+/// place of a result, and blank lines are skipped. Only failing to read or write stops the stream.
+pub fn convert_json_lines(source: impl BufRead, mut out: impl Write) -> std::io::Result<()> {
+    for (index, line) in source.lines().enumerate() {
+        let line = line?;
+        let line = clean_line(index, &line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(line) {
+            Ok(request) => json_response(&request),
+            Err(err) => json!({"line": index + 1, "error": format!("Invalid JSON: {err}")}),
+        };
+        writeln!(out, "{response}")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn json_response(request: &Value) -> Value {
+    let (Some(value), Some(to)) = (request["value"].as_str(), request["to"].as_str()) else {
+        return json!({"error": "Expected an object with \"value\" and \"to\" strings"});
+    };
+    let converted = value
+        .parse::<Temp>()
+        .and_then(|temp| temp.checked_convert_to(TempUnit::parse(to)?));
+    match converted {
+        Ok(new_temp) => json!({"value": value, "to": to, "output": compact(new_temp)}),
+        Err(err) => json!({"value": value, "to": to, "error": err.to_string()}),
+    }
+}
+
+/// Finds the value at a dotted path in a JSON document, such as `readings.0.temp`, where numbers
+/// index into arrays. The JSONPath spellings `$.readings[0].temp` are accepted too.
+pub fn select_json<'a>(document: &'a Value, path: &str) -> Result<&'a Value, String> {
+    let path = path
+        .strip_prefix('$')
+        .unwrap_or(path)
+        .replace('[', ".")
+        .replace(']', "");
+    let mut value = document;
+    let mut walked = Vec::new();
+    for key in path.split('.').filter(|key| !key.is_empty()) {
+        let next = match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => value.get(key),
+        };
+        value = next.ok_or_else(|| match walked.is_empty() {
+            true => format!("{key} isn't in the document"),
+            false => format!("{key} isn't in {}", walked.join(".")),
+        })?;
+        walked.push(key);
+    }
+    Ok(value)
+}
+
+/// Reads the temperature at a dotted path (see [`select_json`]) in a JSON document. It must be a
+/// string like "21.5C".
+pub fn temp_from_json(document: &Value, path: &str) -> Result<Temp, String> {
+    match select_json(document, path)? {
+        Value::String(temp) => temp.parse().map_err(|err: TempError| err.to_string()),
+        other => Err(format!(
+            "Expected a temperature string like \"21.5C\" at {path}, not {other}"
+        )),
+    }
+}
+
+fn json_record(record: &Record, options: &DisplayOptions) -> Value {
+    match &record.result {
+        Ok((_, new_temp)) => {
+            json!({"input": record.input, "output": compact_with(*new_temp, options)})
+        }
+        Err(err) => json!({"input": record.input, "error": err.to_string()}),
+    }
+}
+
+/// The same number-letter notation that temperatures are read in (ex. 212F).
+fn compact(temp: Temp) -> String {
+    format!("{}{}", temp.scalar.normalize(), temp.unit)
+}
+
+/// Like [`compact`], but with the scalar rounded and padded as `options` say (ex. 50.00C with a
+/// precision of 2). The locale and unit style are left out, so the output can still be read back.
+fn compact_with(temp: Temp, options: &DisplayOptions) -> String {
+    let options = DisplayOptions {
+        locale: None,
+        ..*options
+    };
+    format!("{}{}", temp.format_scalar(&options), temp.unit)
+}
+
+/// The column names for [`csv_row`]: the full name of each unit in `order`.
+pub fn csv_header(order: &UnitOrder, separator: char) -> String {
+    order
+        .units()
+        .iter()
+        .map(|unit| unit.full_name())
+        .collect::<Vec<_>>()
+        .join(&separator.to_string())
+}
+
+/// A temperature in each of the units in `order`, as one line of a CSV file (ex. 100,212,373.15).
+/// Only the numbers are written, formatted as `options` say but without a locale, so they can be
+/// appended to a spreadsheet under [`csv_header`].
+pub fn csv_row(temp: Temp, order: &UnitOrder, separator: char, options: &DisplayOptions) -> String {
+    let options = DisplayOptions {
+        locale: None,
+        ..*options
+    };
+    temp.convert_ordered(order)
+        .iter()
+        .map(|converted| converted.format_scalar(&options))
+        .collect::<Vec<_>>()
+        .join(&separator.to_string())
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        column, convert_entries, convert_entries_cached, convert_entries_parallel,
+        convert_json_lines, convert_json_requests, convert_streamed, convert_until_failure,
+        csv_header, csv_row, failed_positions, fix_units, read_entries, read_first_entries,
+        read_lines, read_unit_line, temp_from_json, unit_from_filename, write_in_place,
+        write_joined, write_line_record, write_ndjson_record, write_records, InputFormat,
+        LossyUtf8, OutputFormat, Tally,
+    };
+    use crate::error::{ErrorFormat, TempError};
+    use crate::menu;
+    use crate::temp::{DisplayOptions, Temp, TempUnit, UnitOrder};
+    use crate::template::Template;
+    use serde_json::{json, Value};
+    use std::fs::{self, File};
+    use std::io::{self, BufReader, Read};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    fn run(input: &str, format: InputFormat) -> Result<(String, String), String> {
+        let entries = read_entries(format, input.as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_fahrenheit()));
+        let (mut out, mut errors) = (Vec::new(), Vec::new());
+        write_records(
+            format.into(),
+            &records,
+            &Template::default(),
+            &DisplayOptions::default(),
+            ErrorFormat::Human,
+            &mut out,
+            &mut errors,
+        )
+        .map_err(|err| err.to_string())?;
+        Ok((
+            String::from_utf8(out).map_err(|err| err.to_string())?,
+            String::from_utf8(errors).map_err(|err| err.to_string())?,
+        ))
+    }
+
+    #[test]
+    fn test_json_batch() -> Result<(), String> {
+        assert_eq!(
+            run(r#"["0C","100C"]"#, InputFormat::Json)?.0,
+            "[{\"input\":\"0C\",\"output\":\"32F\"},{\"input\":\"100C\",\"output\":\"212F\"}]\n"
+        );
+        assert_eq!(
+            run(r#"["0C","15d"]"#, InputFormat::Json)?.0,
+            "[{\"input\":\"0C\",\"output\":\"32F\"},\
+            {\"input\":\"15d\",\"error\":\"d is not a valid temperature unit\"}]\n"
+        );
+        assert!(run(r#"{"value":"0C"}"#, InputFormat::Json).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_requests() -> Result<(), String> {
+        let requests = r#"[
+            {"value": "100C", "to": "F"},
+            {"value": "15d", "to": "K"},
+            {"value": "0C", "to": "rankine"},
+            {"value": "212F"},
+            {"value": "373.15K", "to": "celsius"},
+            {"value": "79228162514264337593543950335K", "to": "F"}
+        ]"#;
+        assert_eq!(
+            convert_json_requests(requests.as_bytes())?,
+            json!([
+                {"value": "100C", "to": "F", "output": "212F"},
+                {"value": "15d", "to": "K", "error": "d is not a valid temperature unit"},
+                {"value": "0C", "to": "rankine", "error": "rankine is not a valid temperature unit"},
+                {"error": "Expected an object with \"value\" and \"to\" strings"},
+                {"value": "373.15K", "to": "celsius", "output": "100C"},
+                {
+                    "value": "79228162514264337593543950335K",
+                    "to": "F",
+                    "error": "79228162514264337593543950335 K is too large to convert to F",
+                },
+            ])
+        );
+        assert!(convert_json_requests(r#"{"value": "0C", "to": "F"}"#.as_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_lines() -> Result<(), Box<dyn std::error::Error>> {
+        let stream = concat!(
+            "{\"value\": \"100C\", \"to\": \"F\"}\n",
+            "{\"value\": \"100C\", \"to\":\n",
+            "\n",
+            "{\"value\": \"15d\", \"to\": \"K\"}\r\n",
+            "[\"0C\"]\n",
+            "{\"value\": \"79228162514264337593543950335K\", \"to\": \"F\"}\n",
+            "{\"value\": \"373.15K\", \"to\": \"C\"}",
+        );
+        let mut out = Vec::new();
+        convert_json_lines(stream.as_bytes(), &mut out)?;
+        let responses: Vec<Value> = String::from_utf8(out)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            responses,
+            [
+                json!({"value": "100C", "to": "F", "output": "212F"}),
+                json!({"line": 2, "error": "Invalid JSON: EOF while parsing a value at line 1 column 23"}),
+                json!({"value": "15d", "to": "K", "error": "d is not a valid temperature unit"}),
+                json!({"error": "Expected an object with \"value\" and \"to\" strings"}),
+                json!({
+                    "value": "79228162514264337593543950335K",
+                    "to": "F",
+                    "error": "79228162514264337593543950335 K is too large to convert to F",
+                }),
+                json!({"value": "373.15K", "to": "C", "output": "100C"}),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_temp_from_json() -> Result<(), String> {
+        let document = json!({
+            "sensor": "attic",
+            "readings": [{"temp": "21.5C"}, {"temp": 70}],
+        });
+        assert_eq!(
+            temp_from_json(&document, "readings.0.temp")?,
+            Temp::from_str("21.5C").map_err(|err| err.to_string())?
+        );
+        assert_eq!(
+            temp_from_json(&document, "$.readings[0].temp"),
+            temp_from_json(&document, "readings.0.temp")
+        );
+        assert_eq!(
+            temp_from_json(&document, "readings.2.temp"),
+            Err("2 isn't in readings".to_string())
+        );
+        assert_eq!(
+            temp_from_json(&document, "reading"),
+            Err("reading isn't in the document".to_string())
+        );
+        assert_eq!(
+            temp_from_json(&document, "readings.1.temp"),
+            Err(
+                "Expected a temperature string like \"21.5C\" at readings.1.temp, not 70"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            temp_from_json(&document, "sensor"),
+            Err("atti is not a valid number".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ndjson_batch() -> Result<(), Box<dyn std::error::Error>> {
+        let entries = read_entries(InputFormat::Lines, "100C\n15d\n-40C\n".as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_fahrenheit()));
+        let mut out = Vec::new();
+        write_records(
+            OutputFormat::Ndjson,
+            &records,
+            &Template::default(),
+            &DisplayOptions::default(),
+            ErrorFormat::Human,
+            &mut out,
+            std::io::sink(),
+        )?;
+
+        let lines = String::from_utf8(out)?
+            .lines()
+            .map(serde_json::from_str::<Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            lines,
+            [
+                json!({"input": "100C", "output": "212F"}),
+                json!({"input": "15d", "error": "d is not a valid temperature unit"}),
+                json!({"input": "-40C", "output": "-40F"}),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lines_batch() -> Result<(), String> {
+        assert_eq!(
+            run("0C\n\n15d\n100C\n", InputFormat::Lines)?,
+            (
+                "0 C is equal to 32 F\n100 C is equal to 212 F\n".to_string(),
+                "Unable to convert 15d on line 3: d is not a valid temperature unit\n".to_string()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_windows_file() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("cfk-windows-file-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("input.txt");
+        fs::write(&path, "\u{feff}0C\r\n\r\n100C\r\n-40F\r")?;
+
+        let entries = read_entries(InputFormat::Lines, BufReader::new(File::open(&path)?))?;
+        assert_eq!(
+            entries,
+            [
+                (1, "0C".to_string()),
+                (3, "100C".to_string()),
+                (4, "-40F".to_string())
+            ]
+        );
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_kelvin()));
+        assert!(records.iter().all(|record| record.result.is_ok()));
+
+        let mut source = "\u{feff}F\r\n0C\r\n".as_bytes();
+        assert_eq!(read_unit_line(&mut source), Ok(TempUnit::FAHRENHEIT));
+        assert_eq!(
+            read_entries(InputFormat::Json, "\u{feff}[\"0C\"]".as_bytes())?,
+            [(1, "0C".to_string())]
+        );
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_batch() -> Result<(), String> {
+        assert_eq!(
+            run("0C, \"100C\"\n15d\n", InputFormat::Csv)?.0,
+            "input,output,error\n0C,32F,\n100C,212F,\n15d,,d is not a valid temperature unit\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_row() -> Result<(), Box<dyn std::error::Error>> {
+        let boiling = "100C".parse::<Temp>()?;
+        let options = DisplayOptions::default();
+        let order = UnitOrder::default();
+        assert_eq!(csv_header(&order, ','), "Celsius,Fahrenheit,Kelvin");
+        assert_eq!(csv_row(boiling, &order, ',', &options), "100,212,373.15");
+
+        let order = "K,F".parse::<UnitOrder>()?;
+        let options = DisplayOptions {
+            precision: Some(1),
+            ..options
+        };
+        assert_eq!(csv_header(&order, ';'), "Kelvin;Fahrenheit");
+        assert_eq!(csv_row(boiling, &order, ';', &options), "373.2;212.0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_entries_keeps_failures() {
+        let records = convert_entries(vec![(4, "".to_string())], Temp::from_str, Ok);
+        assert_eq!(records[0].position, 4);
+        assert_eq!(records[0].result, Err(TempError::EmptyInput));
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() -> Result<(), String> {
+        let input: String = (0..20_000)
+            .map(|i| match i % 7 {
+                0 => format!("{i}x\n"),
+                1 => "\n".to_string(),
+                _ => format!("{}.{}F\n", i - 10_000, i % 10),
+            })
+            .collect();
+        let entries = read_entries(InputFormat::Lines, input.as_bytes())?;
+        let convert = |temp: Temp| Ok(temp.to_celsius());
+
+        let parallel = convert_entries_parallel(entries.clone(), Temp::from_str, convert);
+        assert_eq!(parallel, convert_entries(entries, Temp::from_str, convert));
+        assert_eq!(parallel[0].position, 1);
+        assert!(parallel[0].result.is_err());
+        assert_eq!(parallel[1].position, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("cfk-file-round-trip-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let (input_path, output_path) = (dir.join("input.txt"), dir.join("output.txt"));
+        fs::write(&input_path, "0C\n100C\nhot\n")?;
+
+        let entries = read_entries(InputFormat::Lines, BufReader::new(File::open(&input_path)?))?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_kelvin()));
+        write_records(
+            InputFormat::Lines.into(),
+            &records,
+            &Template::default(),
+            &DisplayOptions::default(),
+            ErrorFormat::Human,
+            File::create(&output_path)?,
+            std::io::sink(),
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(&output_path)?,
+            "0 C is equal to 273.15 K\n100 C is equal to 373.15 K\n"
+        );
+        assert_eq!(
+            Tally::of(&records).to_string(),
+            "converted 2, failed 1, total 3"
+        );
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipe_output_is_numeric() -> Result<(), Box<dyn std::error::Error>> {
+        let entries = read_entries(
+            InputFormat::Json,
+            r#"["0C","15d","-40C","1e5K"]"#.as_bytes(),
+        )?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_fahrenheit()));
+        let (mut out, mut errors) = (Vec::new(), Vec::new());
+        write_records(
+            OutputFormat::Lines,
+            &records,
+            &Template::pipe(false),
+            &DisplayOptions::default(),
+            ErrorFormat::Human,
+            &mut out,
+            &mut errors,
+        )?;
+        let out = String::from_utf8(out)?;
+        assert_eq!(out, "32\n-40\n");
+        assert!(out
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '.' | '\n')));
+        assert_eq!(String::from_utf8(errors)?.lines().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tally() -> Result<(), String> {
+        let input = "0C\n15d\n100C\n\n-40F\nhot\n212F\n1e5K\n";
+        let entries = read_entries(InputFormat::Lines, input.as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_kelvin()));
+        assert_eq!(
+            Tally::of(&records),
+            Tally {
+                converted: 4,
+                failed: 3
+            }
+        );
+        assert_eq!(Tally::of(&[]).to_string(), "converted 0, failed 0, total 0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_positions() -> Result<(), String> {
+        let entries = read_entries(InputFormat::Lines, "0C\nhot\n\n5F\n15d\n".as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, Ok);
+        assert_eq!(failed_positions(&records), [2, 5]);
+
+        let entries = read_entries(InputFormat::Csv, "0C,x,y\n5F\n".as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, Ok);
+        assert_eq!(failed_positions(&records), [1]);
+        assert_eq!(
+            Tally::of(&records),
+            Tally {
+                converted: 2,
+                failed: 2
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_units() -> Result<(), String> {
+        let entries = read_entries(InputFormat::Lines, "212d\nhot\n20C\n0kelvins\n".as_bytes())?;
+        let mut records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_celsius()));
+        let mut answers = "2\n\n".as_bytes();
+        let mut prompts = Vec::new();
+        fix_units(
+            &mut records,
+            |entry, err| {
+                menu::prompt_fix(&mut answers, &mut prompts, entry, err)
+                    .ok()
+                    .flatten()
+            },
+            |temp| Ok(temp.to_celsius()),
+        );
+
+        let converted: Vec<Option<String>> = records
+            .iter()
+            .map(|record| {
+                record
+                    .result
+                    .as_ref()
+                    .ok()
+                    .map(|(_, converted)| converted.to_string())
+            })
+            .collect();
+        assert_eq!(
+            converted,
+            [
+                Some("100 C".to_string()),
+                None,
+                Some("20 C".to_string()),
+                None
+            ]
+        );
+        let prompts = String::from_utf8_lossy(&prompts);
+        assert!(prompts.contains("212d: d is not a valid temperature unit"));
+        assert!(!prompts.contains("hot"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_streamed() -> Result<(), Box<dyn std::error::Error>> {
+        let to_fahrenheit = |temp: Temp| Ok(temp.to_fahrenheit());
+        let options = DisplayOptions::default();
+        let mut out = Vec::new();
+        let tally = convert_streamed(
+            InputFormat::Lines,
+            "100C\n\n15d\n-40C\n".as_bytes(),
+            None,
+            false,
+            Temp::from_str,
+            to_fahrenheit,
+            |record| write_ndjson_record(record, &options, &mut out),
+        )?;
+        assert_eq!(
+            tally,
+            Tally {
+                converted: 2,
+                failed: 1
+            }
+        );
+        assert_eq!(
+            String::from_utf8(out)?,
+            "{\"input\":\"100C\",\"output\":\"212F\"}\n\
+             {\"input\":\"15d\",\"error\":\"d is not a valid temperature unit\"}\n\
+             {\"input\":\"-40C\",\"output\":\"-40F\"}\n"
+        );
+
+        // Each record is written before the next line is read
+        let mut source = "0C\n15d\n37C\n".as_bytes();
+        let mut inputs = Vec::new();
+        let tally = convert_streamed(
+            InputFormat::Lines,
+            &mut source,
+            None,
+            true,
+            Temp::from_str,
+            to_fahrenheit,
+            |record| {
+                inputs.push(record.input.clone());
+                Ok(())
+            },
+        )?;
+        assert_eq!(inputs, ["0C", "15d"]);
+        assert_eq!(tally.failed, 1);
+        assert_eq!(source, b"37C\n");
+
+        let tally = convert_streamed(
+            InputFormat::Lines,
+            "0C\n15d\n37C\n".as_bytes(),
+            Some(2),
+            false,
+            Temp::from_str,
+            to_fahrenheit,
+            |_| Ok(()),
+        )?;
+        assert_eq!(tally.converted + tally.failed, 2);
+
+        let failed = convert_streamed(
+            InputFormat::Lines,
+            "0C\n37C\n".as_bytes(),
+            None,
+            false,
+            Temp::from_str,
+            to_fahrenheit,
+            |_| Err(io::Error::other("disk full")),
+        );
+        assert_eq!(
+            failed,
+            Err("Unable to write results: disk full".to_string())
+        );
+
+        let (mut out, mut errors) = (Vec::new(), Vec::new());
+        let template = Template::default();
+        convert_streamed(
+            InputFormat::Lines,
+            "0C\n15d\n100C\n".as_bytes(),
+            None,
+            false,
+            Temp::from_str,
+            to_fahrenheit,
+            |record| {
+                write_line_record(
+                    record,
+                    &template,
+                    &options,
+                    ErrorFormat::Human,
+                    &mut out,
+                    &mut errors,
+                )
+            },
+        )?;
+        assert_eq!(
+            (String::from_utf8(out)?, String::from_utf8(errors)?),
+            run("0C\n15d\n100C\n", InputFormat::Lines)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_until_failure() -> Result<(), String> {
+        let to_kelvin = |temp: Temp| Ok(temp.to_kelvin());
+        let mut source = "0C\n100C\n15d\n37C\n".as_bytes();
+        let records = convert_until_failure(
+            InputFormat::Lines,
+            &mut source,
+            None,
+            Temp::from_str,
+            to_kelvin,
+        )?;
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].input, "15d");
+        assert_eq!(
+            Tally::of(&records),
+            Tally {
+                converted: 2,
+                failed: 1
+            }
+        );
+        // The line after the failure is left unread
+        assert_eq!(source, b"37C\n");
+
+        let source = "0C\n100C\n15d\n".as_bytes();
+        let records = convert_until_failure(
+            InputFormat::Lines,
+            source,
+            Some(2),
+            Temp::from_str,
+            to_kelvin,
+        )?;
+        assert_eq!(Tally::of(&records).failed, 0);
+        assert_eq!(records.len(), 2);
+
+        // The rest of the array isn't checked once a string fails
+        let mut source = r#"["0C", "15d", "37C", 5"#.as_bytes();
+        let records = convert_until_failure(
+            InputFormat::Json,
+            &mut source,
+            None,
+            Temp::from_str,
+            to_kelvin,
+        )?;
+        assert_eq!(records.len(), 2);
+        assert_eq!(source, br#", "37C", 5"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_from_filename() {
+        for (name, unit) in [
+            ("sensor_C.csv", 'C'),
+            ("data/attic_f.txt", 'F'),
+            ("cryo_probe_2_K", 'K'),
+        ] {
+            assert_eq!(
+                unit_from_filename(Path::new(name)).map(char::from),
+                Ok(unit)
+            );
+        }
+        for name in [
+            "sensor.csv",
+            "sensor_X.csv",
+            "sensor_CF.csv",
+            "sensor_C.tar.gz",
+            "_",
+        ] {
+            assert!(unit_from_filename(Path::new(name)).is_err(), "{name}");
+        }
+
+        let entries = read_entries(InputFormat::Lines, "100\n212F\n".as_bytes()).unwrap();
+        let assumed = unit_from_filename(Path::new("boiler_C.csv")).unwrap();
+        let records = convert_entries(
+            entries,
+            |input| Temp::parse_assuming(input, assumed),
+            |temp| Ok(temp.to_celsius()),
+        );
+        assert_eq!(
+            records[0]
+                .result
+                .as_ref()
+                .map(|(original, _)| original.to_string()),
+            Ok("100 C".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unit_line() -> Result<(), String> {
+        let mut source = "F\n0C\n100C".as_bytes();
+        let unit = read_unit_line(&mut source)?;
+        let entries = read_entries(InputFormat::Lines, source)?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.convert_to(unit)));
+        let outputs: Vec<String> = records
+            .iter()
+            .filter_map(|record| record.result.as_ref().ok())
+            .map(|(_, converted)| converted.to_string())
+            .collect();
+        assert_eq!(outputs, ["32 F", "212 F"]);
+
+        assert_eq!(
+            read_unit_line(&mut "0C\n100C".as_bytes()),
+            Err("Expected a unit on the first line of input, but 0C is not a valid temperature unit"
+                .to_string())
+        );
+        assert_eq!(
+            read_unit_line(&mut "".as_bytes()),
+            Err("Expected a unit on the first line of input".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_structured_output_precision() -> Result<(), Box<dyn std::error::Error>> {
+        let entries = read_entries(InputFormat::Lines, "122F\n100F".as_bytes())?;
+        let records = convert_entries(entries, Temp::from_str, |temp| Ok(temp.to_celsius()));
+        let options = DisplayOptions {
+            precision: Some(2),
+            ..DisplayOptions::default()
+        };
+        let write = |format: OutputFormat| -> Result<String, Box<dyn std::error::Error>> {
+            let mut out = Vec::new();
+            write_records(
+                format,
+                &records,
+                &Template::default(),
+                &options,
+                ErrorFormat::Human,
+                &mut out,
+                io::sink(),
+            )?;
+            Ok(String::from_utf8(out)?)
+        };
+        assert_eq!(
+            write(OutputFormat::Lines)?,
+            "122.00 F is equal to 50.00 C\n100.00 F is equal to 37.78 C\n"
+        );
+        assert_eq!(
+            write(OutputFormat::Json)?,
+            "[{\"input\":\"122F\",\"output\":\"50.00C\"},\
+             {\"input\":\"100F\",\"output\":\"37.78C\"}]\n"
+        );
+        assert_eq!(
+            write(OutputFormat::Csv)?,
+            "input,output,error\n122F,50.00C,\n100F,37.78C,\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeated_entries_convert_once() -> Result<(), String> {
+        let input = "0C\n100C\n0C\n15d\n0C\n100C\n15d\n37C\n";
+        let reads = std::cell::Cell::new(0);
+        let parse = |input: &str| {
+            reads.set(reads.get() + 1);
+            Temp::from_str(input)
+        };
+        let convert = |temp: Temp| Ok(temp.to_fahrenheit());
+
+        let entries = read_entries(InputFormat::Lines, input.as_bytes())?;
+        let cached = convert_entries(entries.clone(), parse, convert);
+        assert_eq!(reads.get(), 4);
+
+        reads.set(0);
+        let uncached = convert_entries_cached(entries.clone(), parse, convert, 0);
+        assert_eq!(reads.get(), 8);
+        assert_eq!(cached, uncached);
+
+        // Once 0C and 100C are remembered, 15d and 37C are read every time
+        reads.set(0);
+        let capped = convert_entries_cached(entries, parse, convert, 2);
+        assert_eq!(reads.get(), 5);
+        assert_eq!(capped, uncached);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_joined() -> Result<(), Box<dyn std::error::Error>> {
+        let parse = |input: &str| input.parse::<Temp>();
+        let written = |input: &str, separator: &str| -> std::io::Result<(String, String)> {
+            let entries = read_entries(InputFormat::Lines, input.as_bytes()).unwrap_or_default();
+            let records = convert_entries(entries, parse, |temp| Ok(temp.to_fahrenheit()));
+            let (mut out, mut errors) = (Vec::new(), Vec::new());
+            write_joined(
+                &records,
+                &Template::pipe(false),
+                &DisplayOptions::default(),
+                separator,
+                ErrorFormat::Human,
+                &mut out,
+                &mut errors,
+            )?;
+            Ok((
+                String::from_utf8_lossy(&out).into_owned(),
+                String::from_utf8_lossy(&errors).into_owned(),
+            ))
+        };
+
+        assert_eq!(
+            written("0C\n100C\n-40C\n", ", ")?,
+            ("32, 212, -40\n".to_string(), String::new())
+        );
+        assert_eq!(written("0C\n15d\n100C\n", "\t")?.0, "32\t212\n");
+        assert_eq!(written("0C\n", ",")?.0, "32\n");
+        assert_eq!(written("15d\n", ",")?.0, "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tally_counts() -> Result<(), String> {
+        let entries = read_entries(
+            InputFormat::Lines,
+            "0C\n15d\n\n100C\nhot\n-40F\n".as_bytes(),
+        )?;
+        let records = convert_entries(entries, |input| input.parse::<Temp>(), Ok);
+        let tally = Tally::of(&records);
+        assert_eq!(tally.counts(), "ok=3 err=2");
+        assert_eq!(Tally::default().counts(), "ok=0 err=0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let values: Vec<String> = (0..20_000)
+            .map(|index| match index % 4 {
+                0 => format!("{index}C"),
+                1 => format!("{index}\u{b0}F"),
+                2 => format!("{index} \"K\""),
+                _ => format!("-{index}.5c"),
+            })
+            .collect();
+        let text = serde_json::to_string_pretty(&values)?;
+        // Read through a small buffer, so strings are split across reads
+        let streamed = read_entries(
+            InputFormat::Json,
+            BufReader::with_capacity(7, text.as_bytes()),
+        )?;
+        let buffered: Vec<String> = serde_json::from_str(&text)?;
+        assert_eq!(streamed.len(), buffered.len());
+        assert!(streamed.iter().zip(&buffered).enumerate().all(
+            |(index, ((position, streamed), buffered))| {
+                *position == index + 1 && streamed == buffered
+            }
+        ));
+
+        assert_eq!(
+            read_entries(InputFormat::Json, " [ ] ".as_bytes())?,
+            Vec::<(usize, String)>::new()
+        );
+        for text in [
+            r#"["0C", 5]"#,
+            r#"["0C",]"#,
+            r#"["0C"] x"#,
+            r#"["0C""#,
+            "{}",
+            "",
+        ] {
+            assert!(
+                read_entries(InputFormat::Json, text.as_bytes()).is_err(),
+                "{text}"
+            );
+        }
+        assert_eq!(
+            read_entries(InputFormat::Json, r#"["0C" "5C"]"#.as_bytes()),
+            Err(
+                "Expected a JSON array of strings: expected `,` or `]` at line 1 column 7"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            read_entries(InputFormat::Json, "\u{feff}[\"0C\"]".as_bytes())?,
+            [(1, "0C".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_column() -> Result<(), Box<dyn std::error::Error>> {
+        let table = "sensor-1  12:00   20C   ok\nsensor-2  12:05  100C   ok\nsensor-3  12:10\n";
+        let entries = read_entries(InputFormat::Lines, table.as_bytes())?;
+        let parse = |line: &str| column(line, 3)?.parse::<Temp>();
+        let records = convert_entries(entries, parse, |temp| Ok(temp.to_fahrenheit()));
+        let lines = read_lines(table.as_bytes())?;
+
+        let run = |in_place: bool| -> std::io::Result<(String, String)> {
+            let (mut out, mut errors) = (Vec::new(), Vec::new());
+            match in_place {
+                true => write_in_place(
+                    &lines,
+                    &records,
+                    3,
+                    &DisplayOptions::default(),
+                    ErrorFormat::Human,
+                    &mut out,
+                    &mut errors,
+                )?,
+                false => write_records(
+                    OutputFormat::Lines,
+                    &records,
+                    &Template::pipe(true),
+                    &DisplayOptions::default(),
+                    ErrorFormat::Human,
+                    &mut out,
+                    &mut errors,
+                )?,
+            }
+            Ok((
+                String::from_utf8_lossy(&out).into_owned(),
+                String::from_utf8_lossy(&errors).into_owned(),
+            ))
+        };
+        let missing = "Unable to convert sensor-3  12:10 on line 3: The line has no column 3\n";
+        assert_eq!(
+            run(false)?,
+            ("68F\n212F\n".to_string(), missing.to_string())
+        );
+        assert_eq!(
+            run(true)?,
+            (
+                "sensor-1  12:00   68F   ok\nsensor-2  12:05  212F   ok\nsensor-3  12:10\n"
+                    .to_string(),
+                missing.to_string()
+            )
+        );
+
+        let table = "name  time   temp  state\r\n\n  s1  12:00  20C  ok\n# s2 is down\n";
+        let lines = read_lines(table.as_bytes())?;
+        let entries = lines
+            .iter()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .cloned()
+            .collect();
+        let records = convert_entries(entries, parse, |temp| Ok(temp.to_fahrenheit()));
+        let (mut out, mut errors) = (Vec::new(), Vec::new());
+        let options = DisplayOptions::default();
+        write_in_place(
+            &lines,
+            &records,
+            3,
+            &options,
+            ErrorFormat::Human,
+            &mut out,
+            &mut errors,
+        )?;
+        assert_eq!(
+            String::from_utf8(out)?,
+            "name  time   temp  state\n\n  s1  12:00  68F  ok\n# s2 is down\n"
+        );
+        assert_eq!(
+            String::from_utf8(errors)?,
+            "Unable to convert name  time   temp  state on line 1: temp is not a temperature, which \
+             is written as a number and a unit (ex. 100C)\n\
+             Unable to convert # s2 is down on line 4: is is not a temperature, which is written \
+             as a number and a unit (ex. 100C)\n"
+        );
+        assert_eq!(column("  a\tb  ", 2), Ok("b"));
+        assert_eq!(column("a b", 0), Err(TempError::MissingColumn(0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_first_entries() -> Result<(), String> {
+        let input: String = (0..100).map(|n| format!("{n}C\n")).collect();
+        let mut source = input.as_bytes();
+        let entries = read_first_entries(InputFormat::Lines, &mut source, 5)?;
+        assert_eq!(
+            entries,
+            [(1, "0C"), (2, "1C"), (3, "2C"), (4, "3C"), (5, "4C")]
+                .map(|(line, entry)| (line, entry.to_string()))
+        );
+        assert!(source.starts_with(b"5C\n"), "only the first lines are read");
+
+        let records = convert_entries(entries, str::parse, |temp: Temp| Ok(temp));
+        assert_eq!(records.len(), 5);
+        let mut source = r#"["0C", "1C", "2C"] trailing"#.as_bytes();
+        assert_eq!(
+            read_first_entries(InputFormat::Json, &mut source, 2)?.len(),
+            2
+        );
+        assert_eq!(source, br#", "2C"] trailing"#);
+        assert_eq!(
+            read_first_entries(InputFormat::Lines, "0C\n1C".as_bytes(), 5)?.len(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lossy_utf8() -> Result<(), String> {
+        let input = b"0C\n1\xffC\n\xe2\x84\n100C\r\n\xf0\x9f\x94\xa5C";
+        assert!(read_entries(InputFormat::Lines, &input[..]).is_err());
+
+        let entries = read_entries(InputFormat::Lines, LossyUtf8::new(&input[..]))?;
+        assert_eq!(
+            entries,
+            [
+                (1, "0C"),
+                (2, "1\u{fffd}C"),
+                (3, "\u{fffd}"),
+                (4, "100C"),
+                (5, "🔥C")
+            ]
+            .map(|(line, entry)| (line, entry.to_string()))
+        );
+        let records = convert_entries(entries, str::parse, |temp: Temp| Ok(temp.to_kelvin()));
+        let failed = failed_positions(&records);
+        assert_eq!(failed, [2, 3, 5]);
+        assert_eq!(Tally::of(&records).converted, 2);
+
+        let mut text = String::new();
+        LossyUtf8::new(&b"[\"0C\", \"\xc0C\"]"[..])
+            .read_to_string(&mut text)
+            .map_err(|err| err.to_string())?;
+        assert_eq!(text, "[\"0C\", \"\u{fffd}C\"]");
+        Ok(())
+    }
+}
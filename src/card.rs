@@ -0,0 +1,137 @@
+use crate::temp::{DisplayOptions, Temp, UnitOrder};
+
+/// The characters a card's box is drawn with
+struct Frame {
+    horizontal: char,
+    vertical: char,
+    /// Left, middle, and right joints of the top, divider, and bottom lines
+    joints: [[char; 3]; 3],
+}
+
+const BOX_DRAWING: Frame = Frame {
+    horizontal: '─',
+    vertical: '│',
+    joints: [['┌', '┬', '┐'], ['├', '┼', '┤'], ['└', '┴', '┘']],
+};
+
+const ASCII: Frame = Frame {
+    horizontal: '-',
+    vertical: '|',
+    joints: [['+', '+', '+'], ['+', '+', '+'], ['+', '+', '+']],
+};
+
+/// A boxed table of a temperature in each unit of `order`, with the values lined up on their
+/// decimal points. `ascii` draws the box with plain `+`, `-`, and `|` instead of box-drawing
+/// characters.
+pub fn render(temp: Temp, ascii: bool, order: &UnitOrder) -> String {
+    let frame = match ascii {
+        true => &ASCII,
+        false => &BOX_DRAWING,
+    };
+    let rows: Vec<(&str, String)> = temp
+        .convert_ordered(order)
+        .into_iter()
+        .map(|converted| {
+            (
+                converted.unit.full_name(),
+                converted.format_scalar(&DisplayOptions::default()),
+            )
+        })
+        .collect();
+
+    let split = |value: &str| match value.split_once('.') {
+        Some((int, frac)) => (int.chars().count(), frac.chars().count() + 1),
+        None => (value.chars().count(), 0),
+    };
+    let int_width = rows.iter().map(|(_, value)| split(value).0).max();
+    let frac_width = rows.iter().map(|(_, value)| split(value).1).max();
+    let (int_width, frac_width) = (int_width.unwrap_or(0), frac_width.unwrap_or(0));
+    let name_width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(["Unit".len()])
+        .max()
+        .unwrap_or(0);
+    let value_width = (int_width + frac_width).max("Value".len());
+
+    let line = |[left, middle, right]: [char; 3]| {
+        let bar = |width: usize| frame.horizontal.to_string().repeat(width + 2);
+        format!(
+            "{left}{}{middle}{}{right}",
+            bar(name_width),
+            bar(value_width)
+        )
+    };
+    let row = |name: &str, value: &str| {
+        format!(
+            "{v} {name:<name_width$} {v} {value:<value_width$} {v}",
+            v = frame.vertical
+        )
+    };
+
+    let mut lines = vec![
+        line(frame.joints[0]),
+        row("Unit", "Value"),
+        line(frame.joints[1]),
+    ];
+    lines.extend(rows.iter().map(|(name, value)| {
+        let (int, frac) = split(value);
+        let aligned = format!(
+            "{}{value}{}",
+            " ".repeat(int_width - int),
+            " ".repeat(frac_width - frac)
+        );
+        row(name, &aligned)
+    }));
+    lines.push(line(frame.joints[2]));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::error::TempError;
+    use crate::temp::UnitOrder;
+
+    #[test]
+    fn test_card_alignment() -> Result<(), TempError> {
+        let card = render("-40.125F".parse()?, false, &UnitOrder::default());
+        let widths: Vec<usize> = card.lines().map(|line| line.chars().count()).collect();
+        assert_eq!(widths.len(), 7);
+        assert!(widths.iter().all(|width| *width == widths[0]), "{card}");
+        assert_eq!(card.lines().nth(3), Some("│ Celsius    │ -40.0694444444 │"));
+        assert_eq!(card.lines().nth(4), Some("│ Fahrenheit │ -40.125        │"));
+        assert_eq!(card.lines().nth(5), Some("│ Kelvin     │ 233.0805555556 │"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ascii_card() -> Result<(), TempError> {
+        assert_eq!(
+            render("100C".parse()?, true, &UnitOrder::default()),
+            "+------------+--------+\n\
+             | Unit       | Value  |\n\
+             +------------+--------+\n\
+             | Celsius    | 100    |\n\
+             | Fahrenheit | 212    |\n\
+             | Kelvin     | 373.15 |\n\
+             +------------+--------+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_card_order() -> Result<(), Box<dyn std::error::Error>> {
+        let card = render("100C".parse()?, true, &"K,C".parse()?);
+        let rows: Vec<&str> = card.lines().skip(3).take(3).collect();
+        assert_eq!(
+            rows,
+            [
+                "| Kelvin  | 373.15 |",
+                "| Celsius | 100    |",
+                "+---------+--------+"
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use toml::{Table, Value};
+
+use crate::temp::{DisplayOptions, Temp, TempUnit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A linear temperature scale defined at runtime, where `reading = kelvin * slope + offset`
+/// (ex. Rankine has a slope of 1.8 and an offset of 0).
+pub struct CustomScale {
+    pub name: String,
+    pub slope: Decimal,
+    pub offset: Decimal,
+}
+
+impl CustomScale {
+    /// Reads a temperature, in any unit, on this scale.
+    pub fn from_temp(&self, temp: Temp) -> Decimal {
+        temp.to_kelvin().scalar * self.slope + self.offset
+    }
+
+    /// The temperature, in kelvin, of a reading on this scale.
+    pub fn to_temp(&self, reading: Decimal) -> Temp {
+        Temp {
+            scalar: (reading - self.offset) / self.slope,
+            unit: TempUnit::KELVIN,
+        }
+    }
+
+    /// Whether the scale starts at absolute zero (ex. Rankine), like [`TempUnit::is_absolute`].
+    pub fn is_absolute(&self) -> bool {
+        self.offset.is_zero()
+    }
+
+    /// Writes a reading on this scale, formatting the number as `options` describe.
+    pub fn format_reading(&self, reading: Decimal, options: &DisplayOptions) -> String {
+        // Only the scalar is written, so the unit it's paired with doesn't matter
+        let scalar = Temp {
+            scalar: reading,
+            unit: TempUnit::KELVIN,
+        }
+        .format_scalar(options);
+        format!("{scalar} {}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The scales loaded from a units file, such as
+///
+/// ```toml
+/// [rankine]
+/// slope = 1.8
+/// offset = 0
+/// ```
+///
+/// Numbers may also be written as strings (ex. `slope = "1.8"`) to keep them exact.
+pub struct CustomUnits(Vec<CustomScale>);
+
+impl CustomUnits {
+    /// Reads the scales from a TOML units file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {err}", path.display()))?;
+        text.parse()
+            .map_err(|msg| format!("Invalid units file {}: {msg}", path.display()))
+    }
+
+    /// The scale with this name, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&CustomScale> {
+        let name = name.trim();
+        self.0
+            .iter()
+            .find(|scale| scale.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Reads a number followed by the name of one of the scales, with or without a space between
+    /// them (ex. 491.67rankine or 491.67 rankine).
+    pub fn parse_reading(&self, reading: &str) -> Option<(Decimal, &CustomScale)> {
+        let reading = reading.trim();
+        let number_end = reading
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.')))
+            .unwrap_or(reading.len());
+        let (number, name) = reading.split_at(number_end);
+        Some((Decimal::from_str(number).ok()?, self.get(name)?))
+    }
+}
+
+impl FromStr for CustomUnits {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let table: Table = text.parse().map_err(|err| format!("{err}"))?;
+        table
+            .iter()
+            .map(|(name, fields)| {
+                if TempUnit::parse(name).is_ok() {
+                    return Err(format!("{name} is already a built-in unit"));
+                }
+                let number = |field: &str| match fields.get(field) {
+                    Some(Value::Integer(value)) => Ok(Decimal::from(*value)),
+                    Some(Value::Float(value)) => Decimal::try_from(*value)
+                        .map_err(|_| format!("{name}.{field} is out of range")),
+                    Some(Value::String(value)) => Decimal::from_str(value)
+                        .map_err(|_| format!("{name}.{field} is not a valid number")),
+                    Some(_) => Err(format!("{name}.{field} must be a number")),
+                    None => Err(format!("{name} is missing {field}")),
+                };
+                let (slope, offset) = (number("slope")?, number("offset")?);
+                if slope.is_zero() {
+                    return Err(format!("{name}.slope can't be zero"));
+                }
+                Ok(CustomScale {
+                    name: name.clone(),
+                    slope,
+                    offset,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(CustomUnits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CustomUnits;
+    use crate::temp::Temp;
+    use rust_decimal_macros::dec;
+
+    const UNITS: &str = r#"
+        [rankine]
+        slope = 1.8
+        offset = 0
+
+        [reaumur]
+        slope = "0.8"
+        offset = "-218.52"
+    "#;
+
+    #[test]
+    fn test_custom_round_trip() -> Result<(), String> {
+        let units: CustomUnits = UNITS.parse()?;
+        let rankine = units.get("Rankine").ok_or("rankine is defined")?;
+        let boiling = "100C".parse::<Temp>().map_err(|err| err.to_string())?;
+
+        let reading = rankine.from_temp(boiling);
+        assert_eq!(reading, dec!(671.67));
+        assert_eq!(rankine.to_temp(reading).to_celsius(), boiling);
+
+        let (reading, reaumur) = units.parse_reading("80 reaumur").ok_or("80 reaumur")?;
+        assert_eq!(reaumur.to_temp(reading).to_celsius().scalar, dec!(100));
+        assert_eq!(units.parse_reading("80reaumur"), Some((reading, reaumur)));
+        assert_eq!(units.parse_reading("80 kelvin"), None);
+
+        assert!(rankine.is_absolute());
+        assert!(!reaumur.is_absolute());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_units() {
+        for (text, msg) in [
+            ("[flat]\nslope = 0\noffset = 1", "flat.slope can't be zero"),
+            ("[half]\nslope = 0.5", "half is missing offset"),
+            (
+                "[odd]\nslope = true\noffset = 0",
+                "odd.slope must be a number",
+            ),
+            (
+                "[celsius]\nslope = 1\noffset = -273.15",
+                "celsius is already a built-in unit",
+            ),
+        ] {
+            assert_eq!(text.parse::<CustomUnits>(), Err(msg.to_string()), "{text}");
+        }
+    }
+}
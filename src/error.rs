@@ -0,0 +1,210 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+
+use crate::temp::{Temp, TempUnit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The ways that reading or building a temperature can fail
+pub enum TempError {
+    /// The scalar part couldn't be read as a decimal number
+    InvalidScalar(String),
+    /// Neither a number nor a unit could be found in the input
+    InvalidTemp(String),
+    /// The unit isn't one of the supported temperature units
+    InvalidUnit(char),
+    /// The unit name or symbol isn't one of the supported temperature units
+    InvalidUnitName(String),
+    /// The unit (first) isn't supported, but is close to how the units (second) are written
+    MisspelledUnit(String, Vec<TempUnit>),
+    /// There was nothing to read a temperature from
+    EmptyInput,
+    /// A bare number was given where the unit has to be written out
+    MissingUnit(String),
+    /// The temperature is colder than absolute zero
+    BelowAbsoluteZero(Temp),
+    /// The temperature (first) is colder than the lowest one allowed (second)
+    BelowMinimum(Temp, Temp),
+    /// The temperature (first) is hotter than the highest one allowed (second)
+    AboveMaximum(Temp, Temp),
+    /// Converting the temperature into the unit would go past the largest `Decimal`
+    Overflow(Temp, TempUnit),
+    /// Adding the temperature to a running total would go past the largest `Decimal`
+    TotalOverflow(Temp),
+    /// A temperature difference stated in the unit would go past the largest `Decimal`
+    DeltaOverflow(TempUnit),
+    /// The temperature can't be converted into the unit without rounding
+    Inexact(Temp, TempUnit),
+    /// A rounding step was zero or negative
+    InvalidStep(Decimal),
+    /// An output template couldn't be understood
+    InvalidTemplate(String),
+    /// A binary temperature record couldn't be read
+    InvalidBytes(String),
+    /// A temperature difference wasn't written with its Δ (or d) before the unit
+    InvalidDelta(String),
+    /// A line of a table had fewer columns than the one (counting from 1) to read from
+    MissingColumn(usize),
+    /// A fixed-point reading had more decimal places than a `Decimal` can hold
+    InvalidScale(u32),
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+/// How failures are reported
+pub enum ErrorFormat {
+    /// A sentence describing what went wrong
+    #[default]
+    Human,
+    /// A JSON object with the kind of error, its description, and the input that caused it
+    Json,
+}
+
+impl TempError {
+    /// The name of the variant (ex. InvalidUnit), for tools that react to specific failures.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TempError::InvalidScalar(_) => "InvalidScalar",
+            TempError::InvalidTemp(_) => "InvalidTemp",
+            TempError::InvalidUnit(_) => "InvalidUnit",
+            TempError::InvalidUnitName(_) => "InvalidUnitName",
+            TempError::MisspelledUnit(..) => "MisspelledUnit",
+            TempError::EmptyInput => "EmptyInput",
+            TempError::MissingUnit(_) => "MissingUnit",
+            TempError::BelowAbsoluteZero(_) => "BelowAbsoluteZero",
+            TempError::BelowMinimum(..) => "BelowMinimum",
+            TempError::AboveMaximum(..) => "AboveMaximum",
+            TempError::Overflow(..) => "Overflow",
+            TempError::TotalOverflow(_) => "TotalOverflow",
+            TempError::DeltaOverflow(_) => "DeltaOverflow",
+            TempError::Inexact(..) => "Inexact",
+            TempError::InvalidStep(_) => "InvalidStep",
+            TempError::InvalidTemplate(_) => "InvalidTemplate",
+            TempError::InvalidBytes(_) => "InvalidBytes",
+            TempError::InvalidDelta(_) => "InvalidDelta",
+            TempError::MissingColumn(_) => "MissingColumn",
+            TempError::InvalidScale(_) => "InvalidScale",
+        }
+    }
+
+    /// The error as a JSON object, alongside the input that caused it.
+    pub fn to_json(&self, input: &str) -> Value {
+        json!({"error": self.kind(), "detail": self.to_string(), "input": input})
+    }
+
+    /// Describes the error in `format`. Human descriptions leave the input out, since it is
+    /// usually named in the message already.
+    pub fn report(&self, input: &str, format: ErrorFormat) -> String {
+        match format {
+            ErrorFormat::Human => self.to_string(),
+            ErrorFormat::Json => self.to_json(input).to_string(),
+        }
+    }
+}
+
+impl Display for TempError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TempError::InvalidScalar(scalar) => write!(f, "{scalar} is not a valid number"),
+            TempError::InvalidTemp(input) => write!(
+                f,
+                "{input} is not a temperature, which is written as a number and a unit (ex. 100C)"
+            ),
+            TempError::InvalidUnit(unit) => write!(f, "{unit} is not a valid temperature unit"),
+            TempError::InvalidUnitName(name) => {
+                write!(f, "{name} is not a valid temperature unit")
+            }
+            TempError::MisspelledUnit(name, suggestions) => {
+                let symbols: Vec<String> = suggestions.iter().map(ToString::to_string).collect();
+                let symbols = match symbols.split_last() {
+                    Some((last, rest)) if rest.len() > 1 => {
+                        format!("{}, or {last}", rest.join(", "))
+                    }
+                    Some((last, [first])) => format!("{first} or {last}"),
+                    _ => symbols.concat(),
+                };
+                write!(
+                    f,
+                    "{name} is not a valid temperature unit, did you mean {symbols}?"
+                )
+            }
+            TempError::EmptyInput => write!(f, "No temperature value was given"),
+            TempError::MissingUnit(input) => write!(f, "{input} doesn't say what unit it's in"),
+            TempError::BelowAbsoluteZero(temp) => write!(f, "{temp} is below absolute zero"),
+            TempError::BelowMinimum(temp, min) => {
+                write!(f, "{temp} is below the minimum of {min}")
+            }
+            TempError::AboveMaximum(temp, max) => {
+                write!(f, "{temp} is above the maximum of {max}")
+            }
+            TempError::Overflow(temp, unit) => {
+                write!(f, "{temp} is too large to convert to {unit}")
+            }
+            TempError::TotalOverflow(temp) => {
+                write!(
+                    f,
+                    "Adding {temp} would make the total too large to summarize"
+                )
+            }
+            TempError::DeltaOverflow(unit) => {
+                write!(
+                    f,
+                    "The temperature difference is too large to state in {unit}"
+                )
+            }
+            TempError::Inexact(temp, unit) => {
+                write!(f, "{temp} can't be converted to {unit} exactly")
+            }
+            TempError::MissingColumn(column) => write!(f, "The line has no column {column}"),
+            TempError::InvalidScale(scale) => write!(
+                f,
+                "{scale} decimal places is too many, a temperature can have at most 28"
+            ),
+            TempError::InvalidTemplate(reason) => write!(f, "Invalid output template: {reason}"),
+            TempError::InvalidBytes(reason) => write!(f, "Invalid binary temperature: {reason}"),
+            TempError::InvalidDelta(input) => write!(
+                f,
+                "{input} is not a temperature difference, which is written like 5ΔC or 5dC"
+            ),
+            TempError::InvalidStep(step) => {
+                write!(
+                    f,
+                    "{step} is not a valid step, it must be greater than zero"
+                )
+            }
+        }
+    }
+}
+
+impl Error for TempError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorFormat, TempError};
+    use crate::temp::Temp;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_errors() {
+        let err = "15d".parse::<Temp>().unwrap_err();
+        assert_eq!(
+            err.to_json("15d"),
+            json!({
+                "error": "InvalidUnit",
+                "detail": "d is not a valid temperature unit",
+                "input": "15d"
+            })
+        );
+        assert_eq!(
+            err.report("15d", ErrorFormat::Json),
+            r#"{"error":"InvalidUnit","detail":"d is not a valid temperature unit","input":"15d"}"#
+        );
+        assert_eq!(
+            err.report("15d", ErrorFormat::Human),
+            "d is not a valid temperature unit"
+        );
+        assert_eq!(TempError::EmptyInput.kind(), "EmptyInput");
+    }
+}
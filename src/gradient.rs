@@ -0,0 +1,92 @@
+use crate::temp::{Temp, TempDelta};
+
+/// The most blocks a strip is drawn with, which keeps a tiny step from flooding the terminal
+pub const MAX_BLOCKS: usize = 1000;
+/// Blocks from coldest to hottest, for when there's no color to tell them apart
+const PLAIN_RAMP: [char; 9] = ['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// A strip with one block for each temperature from `start` to `end`, `step` apart. With `color`,
+/// each block is drawn in 24-bit color from blue at `start` to red at `end`. Without it (ex. when
+/// writing to a file), the blocks are plain characters that get denser towards `end`.
+pub fn render(start: Temp, end: Temp, step: TempDelta, color: bool) -> Result<String, String> {
+    let temps: Vec<Temp> = Temp::range(start, end, step)
+        .map_err(|err| err.to_string())?
+        .take(MAX_BLOCKS + 1)
+        .collect();
+    if temps.len() > MAX_BLOCKS {
+        return Err(format!(
+            "A step of {} from {start} to {end} would draw more than {MAX_BLOCKS} blocks",
+            step.format_in(start.unit).trim_start_matches('+'),
+        ));
+    }
+
+    Ok(temps
+        .iter()
+        .map(|temp| {
+            let fraction: f64 = temp
+                .fraction_of_clamped(start, end)
+                .and_then(|fraction| fraction.try_into().ok())
+                .unwrap_or(0.0);
+            match color {
+                true => {
+                    let [red, green, blue] = heat_color(fraction);
+                    format!("\x1b[38;2;{red};{green};{blue}m█\x1b[0m")
+                }
+                false => {
+                    let level = (fraction * (PLAIN_RAMP.len() - 1) as f64).round() as usize;
+                    PLAIN_RAMP[level].to_string()
+                }
+            }
+        })
+        .collect())
+}
+
+/// The color of a point `fraction` of the way along the strip, sweeping the hue from blue
+/// through green and yellow to red.
+fn heat_color(fraction: f64) -> [u8; 3] {
+    let hue = 240.0 * (1.0 - fraction) / 60.0;
+    let rising = ((1.0 - (hue % 2.0 - 1.0).abs()) * 255.0).round() as u8;
+    match hue as u8 {
+        0 => [255, rising, 0],
+        1 => [rising, 255, 0],
+        2 => [0, 255, rising],
+        _ => [0, rising, 255],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{heat_color, render};
+    use crate::temp::{Temp, TempDelta, TempUnit};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_gradient_block_count() -> Result<(), Box<dyn std::error::Error>> {
+        let (start, end) = ("0C".parse::<Temp>()?, "40C".parse::<Temp>()?);
+        let step = TempDelta::of(dec!(1), TempUnit::CELSIUS);
+
+        let plain = render(start, end, step, false)?;
+        assert_eq!(plain.chars().count(), 41);
+        assert!(plain.starts_with('.') && plain.ends_with('@'), "{plain}");
+
+        let colored = render(start, end, step, true)?;
+        assert_eq!(colored.matches('█').count(), 41);
+        assert!(colored.starts_with("\x1b[38;2;0;0;255m█"), "{colored:?}");
+        assert!(
+            colored.ends_with("\x1b[38;2;255;0;0m█\x1b[0m"),
+            "{colored:?}"
+        );
+
+        let tiny = TempDelta::of(dec!(0.001), TempUnit::CELSIUS);
+        assert!(render(start, end, tiny, false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_heat_colors() {
+        assert_eq!(heat_color(0.0), [0, 0, 255]);
+        assert_eq!(heat_color(0.5), [0, 255, 0]);
+        assert_eq!(heat_color(0.75), [255, 255, 0]);
+        assert_eq!(heat_color(1.0), [255, 0, 0]);
+    }
+}
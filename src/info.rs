@@ -0,0 +1,76 @@
+use rust_decimal::Decimal;
+
+use crate::temp::TempUnit;
+
+/// How to get a reading in `unit` from one in kelvin (ex. F = K × 1.8 - 459.67).
+pub fn formula(unit: TempUnit) -> String {
+    let (slope, offset) = (unit.slope().normalize(), unit.offset().normalize());
+    let scaled = match slope == Decimal::ONE {
+        true => "K".to_string(),
+        false => format!("K × {slope}"),
+    };
+    let shifted = match offset {
+        offset if offset.is_zero() => scaled,
+        offset if offset.is_sign_negative() => format!("{scaled} - {}", offset.abs()),
+        offset => format!("{scaled} + {offset}"),
+    };
+    format!("{unit} = {shifted}")
+}
+
+/// The version of cfk, followed by a table of every supported unit with its symbol and how it
+/// relates to kelvin.
+pub fn render() -> String {
+    let rows: Vec<[String; 4]> = TempUnit::all()
+        .map(|unit| {
+            [
+                unit.to_string(),
+                unit.full_name().to_string(),
+                unit.si_symbol().to_string(),
+                formula(unit),
+            ]
+        })
+        .collect();
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let (name_width, symbol_width) = (width(1), width(2));
+
+    let mut lines = vec![
+        format!("cfk {}", env!("CARGO_PKG_VERSION")),
+        String::new(),
+        "Supported units:".to_string(),
+    ];
+    lines.extend(rows.iter().map(|[letter, name, symbol, formula]| {
+        format!("  {letter}  {name:<name_width$}  {symbol:<symbol_width$}  {formula}")
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{formula, render};
+    use crate::temp::TempUnit;
+
+    #[test]
+    fn test_info_lists_every_unit() {
+        let info = render();
+        assert!(info.starts_with(&format!("cfk {}\n", env!("CARGO_PKG_VERSION"))));
+        for unit in TempUnit::all() {
+            let row = format!("  {unit}  {}", unit.full_name());
+            let line = info.lines().find(|line| line.starts_with(&row));
+            let line = line.unwrap_or_else(|| panic!("{unit} is missing from\n{info}"));
+            assert!(line.contains(unit.si_symbol()), "{line}");
+            assert!(line.ends_with(&formula(unit)), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_formulas() {
+        assert_eq!(formula(TempUnit::CELSIUS), "C = K - 273.15");
+        assert_eq!(formula(TempUnit::FAHRENHEIT), "F = K × 1.8 - 459.67");
+        assert_eq!(formula(TempUnit::KELVIN), "K = K");
+    }
+}
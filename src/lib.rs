@@ -0,0 +1,14 @@
+pub mod batch;
+pub mod card;
+pub mod custom;
+pub mod error;
+pub mod gradient;
+pub mod info;
+pub mod menu;
+pub mod region;
+pub mod repl;
+pub mod self_test;
+pub mod stats;
+pub mod temp;
+pub mod template;
+pub mod watch;
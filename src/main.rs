@@ -1,34 +1,1629 @@
-mod temp;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::hint;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use num_format::Locale;
+use rust_decimal::Decimal;
 
-use temp::Temp;
+use cfk::batch::{self, InputFormat, OutputFormat, Tally};
+use cfk::card;
+use cfk::custom::CustomUnits;
+use cfk::error::{ErrorFormat, TempError};
+use cfk::gradient;
+use cfk::info;
+use cfk::menu;
+use cfk::region;
+use cfk::repl::{self, Repl};
+use cfk::stats;
+use cfk::temp::{
+    self, DisplayOptions, Resolution, RoundMode, Temp, TempCoefficient, TempDelta, TempRate,
+    TempUnit, UnitOrder,
+};
+use cfk::template::Template;
+use cfk::watch::Tail;
 
 #[derive(Parser, Debug)]
-#[command(about)]
+#[command(
+    about,
+    args_conflicts_with_subcommands = true,
+    override_usage = "cfk [OPTIONS] [original] <unit>\n       cfk <COMMAND>"
+)]
 /// A script to convert between Celsius, Fahrenheit, and Kelvin.
 struct Unit {
-    #[arg(id = "original")]
-    /// The original value, provided as a number-letter combo (ex. 32F, 0C, 273K)
-    original: Temp,
+    #[arg(id = "original", allow_hyphen_values = true)]
+    /// The original value, provided as a number-letter combo (ex. 32F, 0C, 273K). When left
+    /// out, values are read from stdin instead
+    original: Option<String>,
 
     #[arg(id = "unit")]
-    /// The unit to convert into
-    new_unit: char
+    /// The unit to convert into, as a letter, name, or symbol (ex. F, fahrenheit, °F)
+    new_unit: Option<String>,
+
+    #[arg(short, long, conflicts_with = "unit")]
+    /// The unit to convert into, instead of giving it after the original value (ex. cfk 100C
+    /// --to F). A lone argument is then always the original value
+    to: Option<TempUnit>,
+
+    #[arg(long)]
+    /// Convert into the unit this country uses (ex. US for F, DE for C) when no unit is given.
+    /// `auto` reads the country from the first of LC_ALL, LC_MEASUREMENT, or LANG that is set. A
+    /// unit given on the command line always wins. Codes that aren't ISO 3166's are rejected
+    country: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["unit", "to", "country", "interactive_select", "unit_from_stdin"]
+    )]
+    /// Convert every value, whatever its unit, into the base unit (kelvin), so that results all
+    /// share one unit. A lone argument is then always the original value
+    base_unit: bool,
+
+    #[arg(long)]
+    /// When only a temperature is given and stdin is a terminal, pick the unit to convert into
+    /// from a menu
+    interactive_select: bool,
+
+    #[arg(long, value_name = "PATH")]
+    /// Also accept the linear scales defined in this TOML file as the original's or the target's
+    /// unit. Each scale is a table with `reading = kelvin * slope + offset` (ex. [rankine] with
+    /// slope = 1.8 and offset = 0). --strict, --min and --max still apply to a conversion with
+    /// one of these scales, but --snap, --whole, --sensor, --pipe and --template can't be used
+    units_file: Option<PathBuf>,
+
+    #[arg(long, value_parser = temp::parse_locale)]
+    /// Format numbers with the separators of a locale (ex. de-DE, fr-FR, en-US)
+    locale: Option<Locale>,
+
+    #[arg(long, global = true)]
+    /// Keep trailing zeros, showing each value at the scale it was computed with (ex. 100.00C is
+    /// equal to 212.0000F)
+    no_normalize: bool,
+
+    #[arg(long, global = true, value_name = "PLACES")]
+    /// Show exactly this many decimal places, padding with zeros (ex. 50.00), in every output
+    /// format. By default, values are shown in full unless they run past 10 places
+    precision: Option<u32>,
+
+    #[arg(long, global = true, value_enum, value_name = "MODE")]
+    /// How --precision and --whole round. Halves go to the even neighbour by default (ex. 0.125 to
+    /// 0.12), so rounding many values doesn't bias them. Without --precision, long values are
+    /// rounded half away from zero unless this says otherwise
+    round_mode: Option<RoundMode>,
+
+    #[arg(long, global = true)]
+    /// Write values in scientific notation (ex. 6.96e7 F), with --precision setting the decimal
+    /// places of the mantissa
+    scientific: bool,
+
+    #[arg(long, conflicts_with_all = ["precision", "scientific"])]
+    /// Show each result with as many decimal places as its original was written with (ex. 100.0C
+    /// is equal to 212.0F), rounding or padding it to fit
+    match_input_precision: bool,
+
+    #[arg(
+        long,
+        global = true,
+        requires = "precision",
+        conflicts_with = "no_normalize"
+    )]
+    /// With --precision, still drop the trailing zeros after rounding (ex. 50 rather than 50.00)
+    trim_zeros: bool,
+
+    #[arg(long)]
+    /// Round the converted value to the nearest multiple of this step (ex. 0.5)
+    snap: Option<Decimal>,
+
+    #[arg(long, conflicts_with = "snap")]
+    /// Round the converted value to the nearest whole degree, with halves going to the even one
+    /// unless --round-mode says otherwise
+    whole: bool,
+
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["snap", "whole"])]
+    /// Round the converted value to the nearest reading this sensor can give: ds18b20 (0.0625 C),
+    /// tmp117 (0.0078125 C), or sht3x (0.01 C)
+    sensor: Option<Resolution>,
+
+    #[arg(long)]
+    /// Lay out the result using {input_scalar}, {input_unit}, {output_scalar}, and {output_unit}
+    /// placeholders. Literal braces are written as {{ and }}
+    template: Option<Template>,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        allow_hyphen_values = true,
+        default_value = ""
+    )]
+    /// Write this text before each result (ex. "Temp: "), including with --pipe
+    output_prefix: String,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        allow_hyphen_values = true,
+        default_value = ""
+    )]
+    /// Write this text after each result (ex. " (converted)"), including with --pipe
+    output_suffix: String,
+
+    #[arg(long, conflicts_with_all = ["pipe", "output_format"])]
+    /// End each result with how far it lands from the original when converted back, as written
+    /// (ex. 37.7777777778 C (±0.00000000004 F)), unless it converts back exactly
+    annotate_drift: bool,
+
+    #[arg(long, conflicts_with_all = ["template", "locale", "output_format"])]
+    /// Write only the converted numbers to stdout, one per line, and everything else (including
+    /// failures) to stderr
+    pipe: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["pipe", "template", "emoji", "snap", "whole", "output_prefix", "output_suffix"]
+    )]
+    /// Write a single conversion as a formula to paste into a spreadsheet (ex. =(100*1.8)+32)
+    as_equation: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["as_equation", "pipe", "template", "emoji", "snap", "whole", "output_prefix", "output_suffix"]
+    )]
+    /// Write how a single conversion is worked out as JSON: its formula (ex. (F-32)*5/9), each
+    /// step with its constant and the value it leaves, and the result
+    explain_json: bool,
+
+    #[arg(long)]
+    /// Start the result of a single conversion with an emoji for how hot it is (🥶, 🌡️, or 🥵).
+    /// Ignored with --pipe, which always writes bare numbers
+    emoji: bool,
+
+    #[arg(long, requires = "pipe")]
+    /// Follow each number written by --pipe with its unit letter (ex. 212F)
+    pipe_unit: bool,
+
+    #[arg(long)]
+    /// Warn on stderr when converting back doesn't recover the original within the tolerance.
+    /// Values read from stdin that repeat are only checked, and warned about, the first time
+    round_trip_check: bool,
+
+    #[arg(long)]
+    /// Fail, rather than round, when a result can't be written exactly (ex. 100F in C)
+    require_exact: bool,
+
+    #[arg(long)]
+    /// Forgive common typos in temperatures, such as quotes, a space before the unit, or trailing
+    /// punctuation (ex. "100 C.")
+    loose: bool,
+
+    #[arg(
+        long,
+        alias = "input-scale-factor",
+        value_name = "FACTOR",
+        allow_hyphen_values = true
+    )]
+    /// Multiply each value read by this factor before converting it, such as 0.1 for readings in
+    /// tenths of a degree (ex. 985F becomes 98.5F)
+    scale: Option<Decimal>,
+
+    #[arg(long, conflicts_with = "loose", value_parser = clap::value_parser!(u32).range(2..=36))]
+    /// Read temperatures as whole numbers in this radix, optionally prefixed with 0x, 0o, or 0b
+    /// (ex. 0x64C is 100 C with --radix 16). The unit is still written normally at the end
+    radix: Option<u32>,
+
+    #[arg(long)]
+    /// Reject values written without a unit (ex. 100), rather than assuming one. This overrides
+    /// --unit-from-filename
+    require_unit: bool,
+
+    #[arg(long)]
+    /// Reject temperatures below absolute zero, and conversions too large to represent. Results
+    /// are also never rounded to below absolute zero
+    strict: bool,
+
+    #[arg(long, allow_hyphen_values = true)]
+    /// Reject temperatures colder than this one, in any unit (ex. 0C)
+    min: Option<Temp>,
+
+    #[arg(long, allow_hyphen_values = true)]
+    /// Reject temperatures hotter than this one, in any unit (ex. 150C)
+    max: Option<Temp>,
+
+    #[arg(long, value_enum, default_value_t)]
+    /// How values read from stdin are laid out
+    input_format: InputFormat,
+
+    #[arg(long, value_enum)]
+    /// How results of values read from stdin are written. Matches the input format by default
+    output_format: Option<OutputFormat>,
+
+    #[arg(long, value_name = "TEXT", allow_hyphen_values = true)]
+    /// Write the results of values read from stdin on one line, with this between them (ex. ", ",
+    /// or $'\t' for tabs), rather than one per line. Only for results written one per line, so
+    /// not with JSON or CSV input unless --output-format lines is given. The line is written once
+    /// every value has been converted
+    separator: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["original", "unit", "to", "country", "from_file", "interactive_select"]
+    )]
+    /// Read the unit to convert into from the first line of stdin, and values from the lines after
+    unit_from_stdin: bool,
+
+    #[arg(long)]
+    /// Read values from a file instead of stdin
+    from_file: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Read bytes from stdin or a file that aren't valid UTF-8 as �, rather than giving up on the
+    /// whole input. Lines with such bytes then fail on their own
+    utf8_lossy: bool,
+
+    #[arg(long, requires = "from_file", alias = "input-unit-from-filename")]
+    /// Read unit-less values in the unit named at the end of the --from-file name (ex. a file
+    /// called sensor_C.csv holds Celsius)
+    unit_from_filename: bool,
+
+    #[arg(long)]
+    /// Write the results of values read from stdin or a file to this file instead of stdout
+    to_file: Option<PathBuf>,
+
+    #[arg(long, hide = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    /// Convert a single temperature N times, writing the total and average time taken to stderr.
+    /// The result is still only written once
+    repeat: Option<u32>,
+
+    #[arg(long, conflicts_with_all = ["keep_going", "batch_stats", "dry_run"])]
+    /// Stop at the first value from stdin or a file that can't be converted, after writing the
+    /// results before it
+    fail_fast: bool,
+
+    #[arg(long)]
+    /// Skip values from stdin or a file that can't be converted, and carry on with the rest. This
+    /// is the default. Either way, the exit code is nonzero if any value failed
+    keep_going: bool,
+
+    #[arg(long, conflicts_with_all = ["batch_stats", "dry_run"])]
+    /// When a value from a file has a unit that can't be read, ask which unit it's in on the
+    /// terminal instead of skipping it. Does nothing unless stdin is a terminal
+    interactive_fix: bool,
+
+    #[arg(long, conflicts_with_all = ["output_format", "batch_stats", "dry_run"])]
+    /// Line up the numbers in the results of values from stdin or a file in columns, on their
+    /// decimal points. Nothing is written until every value has been converted
+    align_output: bool,
+
+    #[arg(long)]
+    /// Convert values read from stdin on several threads. Nothing is written until every value
+    /// has been converted
+    parallel: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "input_format"
+    )]
+    /// Read each line from stdin or a file as a table, split on whitespace, and take the
+    /// temperature from column N (counting from 1). Lines with fewer columns fail
+    input_column: Option<u32>,
+
+    #[arg(
+        long,
+        requires = "input_column",
+        conflicts_with_all = ["output_format", "align_output", "separator", "pipe", "template", "count_only", "batch_stats", "dry_run", "group_by_unit", "fail_fast", "preview"]
+    )]
+    /// With --input-column, write each line back with only that column converted (ex. 212F),
+    /// keeping the other columns and spacing as they were. Lines that can't be converted, like
+    /// headers, are written back unchanged
+    in_place: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["original", "unit", "to", "unit_from_stdin", "to_file", "batch_stats"]
+    )]
+    /// Only check that every value from stdin or a file can be read, without converting anything.
+    /// How many could and couldn't, and the lines of those that couldn't, are written to stderr.
+    /// Fails if any couldn't be read
+    dry_run: bool,
+
+    #[arg(long, conflicts_with_all = ["input_format", "output_format", "pipe"])]
+    /// Instead of converting values from stdin or a file one by one, print their count, minimum,
+    /// maximum, and mean. Lines that can't be read are skipped, and if none could be, it fails
+    batch_stats: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["original", "unit", "to", "unit_from_stdin", "input_format", "output_format", "pipe", "batch_stats", "dry_run"]
+    )]
+    /// Instead of converting values from stdin or a file, print their count, minimum, maximum,
+    /// and mean for each unit they were written in, in that unit. Lines that can't be read are
+    /// skipped, and if none could be, it fails
+    group_by_unit: bool,
+
+    #[arg(long)]
+    /// After converting values from stdin or a file, print how many converted and failed to
+    /// stderr. This is always done when reading or writing files
+    summary: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["output_format", "align_output", "separator", "pipe", "summary", "batch_stats", "dry_run"]
+    )]
+    /// Instead of the results of values from stdin or a file, write only how many converted and
+    /// failed, as `ok=980 err=20`. Nothing else is written, and the exit code is nonzero if any
+    /// value failed
+    count_only: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["batch_stats", "group_by_unit", "dry_run"]
+    )]
+    /// Only convert the first N values from stdin or a file, then stop without reading the rest
+    preview: Option<usize>,
+
+    #[arg(long, value_enum, default_value_t)]
+    /// How failures to read or convert a value are written to stderr
+    error_format: ErrorFormat,
+
+    #[arg(long, global = true, default_value_t = temp::DEFAULT_TOLERANCE)]
+    /// How far apart, in kelvin, temperatures can be while still counting as equal
+    tolerance: Decimal,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compare two temperatures, regardless of their units
+    Compare {
+        #[arg(allow_hyphen_values = true)]
+        first: Temp,
+        #[arg(allow_hyphen_values = true)]
+        second: Temp,
+    },
+    /// Print how much hotter the first temperature is than the second, as a signed difference
+    Diff {
+        #[arg(allow_hyphen_values = true)]
+        first: Temp,
+        #[arg(allow_hyphen_values = true)]
+        second: Temp,
+        #[arg(long = "in")]
+        /// The unit to give the difference in degrees of. Defaults to the unit of the first
+        unit: Option<TempUnit>,
+    },
+    /// Print a table of a temperature in every unit
+    Card {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+        #[arg(long)]
+        /// Draw the table with plain ASCII characters
+        ascii: bool,
+        #[arg(long, value_name = "UNITS", default_value = "C,F,K")]
+        /// Which units to list, in order (ex. K,C)
+        order: UnitOrder,
+    },
+    /// Print a temperature in every unit as one CSV line (ex. 100,212,373.15), for appending to a
+    /// spreadsheet. The values are written with --precision, --round-mode, --scientific,
+    /// --trim-zeros and --no-normalize, like conversions are
+    CsvRow {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+        #[arg(long)]
+        /// Print a line of column names before the values
+        header: bool,
+        #[arg(long, default_value_t = ',')]
+        /// The character to put between the values
+        separator: char,
+        #[arg(long, value_name = "UNITS", default_value = "C,F,K")]
+        /// Which units to list, in order (ex. K,C)
+        order: UnitOrder,
+    },
+    /// Name the everyday temperature (freezing, room or body temperature, boiling) that a
+    /// temperature is within the tolerance of
+    Classify {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+    },
+    /// Print evenly spaced temperatures between two temperatures, including both
+    Linspace {
+        #[arg(allow_hyphen_values = true)]
+        start: Temp,
+        #[arg(allow_hyphen_values = true)]
+        end: Temp,
+        count: usize,
+        /// The unit to print them in. Defaults to the unit of the start
+        unit: Option<TempUnit>,
+    },
+    /// Draw a strip of blocks from one temperature to another, colored from blue to red. Plain
+    /// characters are drawn instead when stdout isn't a terminal or NO_COLOR is set
+    Gradient {
+        #[arg(long, allow_hyphen_values = true)]
+        from: Temp,
+        #[arg(long, allow_hyphen_values = true)]
+        to: Temp,
+        #[arg(long, allow_hyphen_values = true, default_value = "1C")]
+        /// How far apart the blocks are, written as a temperature difference (ex. 2F)
+        step: Temp,
+        #[arg(long)]
+        /// Draw plain characters even on a terminal
+        plain: bool,
+    },
+    /// Say whether water is solid, liquid, or gas at a temperature, at one atmosphere
+    Phase {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+    },
+    /// Say how a temperature feels, from freezing to scalding
+    Describe {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+    },
+    /// Guess which unit a bare number was read in, from how plausible it is in each one. Every
+    /// reading is listed with its conversion, most plausible first
+    Reverse {
+        #[arg(allow_hyphen_values = true)]
+        value: Decimal,
+        #[arg(long)]
+        /// The unit to convert each reading to
+        to: TempUnit,
+    },
+    /// Convert a temperature found in a JSON file
+    FromJson {
+        file: PathBuf,
+        #[arg(long)]
+        /// Where the temperature is, as a dotted path like readings.0.temp
+        path: String,
+        unit: TempUnit,
+    },
+    /// Convert each line appended to a file, as it arrives, until interrupted with Ctrl-C
+    Watch {
+        path: PathBuf,
+        unit: TempUnit,
+        #[arg(long, value_name = "MILLISECONDS", default_value_t = 500)]
+        /// How often to check the file for new lines
+        interval: u64,
+    },
+    /// Convert lines like `100C F` interactively. `history` lists earlier lines
+    Repl,
+    /// Restate a per-degree coefficient (ex. 0.004 per C) per degree of another unit
+    Coeff {
+        #[arg(allow_hyphen_values = true)]
+        value: Decimal,
+        #[arg(long)]
+        /// The unit the coefficient is given per degree of
+        per: TempUnit,
+        #[arg(long)]
+        /// The unit to restate it per degree of
+        to: TempUnit,
+    },
+    /// Convert structured requests, for use from other programs
+    Convert {
+        #[arg(long, required_unless_present = "stdin_json_lines")]
+        /// Read a JSON array of {"value": "100C", "to": "F"} objects from stdin, and write a JSON
+        /// array of their results or errors in the same order
+        batch_json: bool,
+        #[arg(long, conflicts_with = "batch_json")]
+        /// Read one {"value": "100C", "to": "F"} object per line from stdin, and write each
+        /// result or error as a line of JSON as soon as it's converted
+        stdin_json_lines: bool,
+    },
+    /// Restate a rate of change (ex. 2C per minute) in degrees of another unit
+    Rate {
+        #[arg(allow_hyphen_values = true)]
+        /// The change in temperature, ex. 2C
+        change: Temp,
+        #[arg(long, default_value = "minute")]
+        /// The time the change happens over, which is kept as is
+        per: String,
+        #[arg(long)]
+        /// The unit to restate the change in
+        to: TempUnit,
+    },
+    /// Sort temperatures from coldest to hottest, regardless of their units. Options go before
+    /// the temperatures
+    Sort {
+        #[arg(required = true, allow_hyphen_values = true)]
+        temps: Vec<Temp>,
+        #[arg(long)]
+        /// Convert the sorted temperatures into this unit
+        to: Option<TempUnit>,
+        #[arg(long)]
+        /// Sort from hottest to coldest instead
+        desc: bool,
+    },
+    /// Check the conversions against well-known temperatures
+    SelfTest,
+    /// Convert a temperature into every other unit and back, showing how far each path drifts.
+    /// Fails if any drifts by more than the tolerance
+    RoundTrip {
+        #[arg(allow_hyphen_values = true)]
+        temp: Temp,
+    },
+    /// Print the version, along with the supported units and how each relates to kelvin
+    Info,
+    #[cfg(feature = "schema")]
+    /// Print the JSON Schema of a serialized temperature
+    Schema,
+}
+
+impl Unit {
+    /// Works out the temperature and unit from the positional arguments. When only one is given
+    /// it is the unit, and temperatures are read from stdin instead. With `--country`, a lone
+    /// temperature or no arguments at all are also accepted, and convert into the country's unit.
+    fn positionals(&self) -> Result<(Option<Temp>, TempUnit), clap::Error> {
+        if let Some(to) = self.to.or(self.base_unit.then_some(TempUnit::BASE)) {
+            let original = self.original.as_deref().map(|temp_str| {
+                self.parse_temp(temp_str)
+                    .map_err(|err| self.invalid_input(temp_str, "[original]", err))
+            });
+            return Ok((original.transpose()?, to));
+        }
+
+        let country_unit = self.country_unit()?;
+        let (original, new_unit) = match (&self.original, &self.new_unit, country_unit) {
+            (Some(original), Some(new_unit), _) => (Some(original), Some(new_unit)),
+            (Some(original), None, _)
+                if (country_unit.is_some() || self.interactive_select)
+                    && self.parse_temp(original).is_ok() =>
+            {
+                (Some(original), None)
+            }
+            (Some(new_unit), None, _) => (None, Some(new_unit)),
+            (None, _, Some(_)) => (None, None),
+            (None, _, None) => return Err(missing_unit()),
+        };
+
+        let original = original
+            .map(|temp_str| {
+                self.parse_temp(temp_str)
+                    .map_err(|err| self.invalid_input(temp_str, "[original]", err))
+            })
+            .transpose()?;
+        let new_unit = match (new_unit, country_unit) {
+            (Some(new_unit), _) => TempUnit::parse(new_unit)
+                .map_err(|err| self.invalid_input(new_unit, "<unit>", err))?,
+            (None, Some(country_unit)) => country_unit,
+            (None, None) => select_unit().ok_or_else(missing_unit)?,
+        };
+        Ok((original, new_unit))
+    }
+
+    /// The unit of the `--country` option, if it was given.
+    fn country_unit(&self) -> Result<Option<TempUnit>, clap::Error> {
+        let country = match self.country.as_deref() {
+            Some("auto") => region::detect_country().ok_or_else(|| {
+                Unit::command().error(
+                    ErrorKind::ValueValidation,
+                    format!(
+                        "--country auto couldn't find a country in {}",
+                        region::LOCALE_VARS.join(", ")
+                    ),
+                )
+            })?,
+            Some(country) => country.to_string(),
+            None => return Ok(None),
+        };
+        match region::unit_for_country(&country) {
+            Some(unit) => Ok(Some(unit)),
+            None => {
+                let given = match self.country.as_deref() {
+                    Some("auto") => format!("--country auto found {country}, which"),
+                    _ => format!("--country {country}"),
+                };
+                Err(Unit::command().error(
+                    ErrorKind::ValueValidation,
+                    format!("{given} isn't a two-letter ISO 3166 country code (ex. US)"),
+                ))
+            }
+        }
+    }
+
+    /// Reads a temperature, forgiving typos with `--loose`.
+    fn parse_temp(&self, temp_str: &str) -> Result<Temp, TempError> {
+        if self.require_unit && temp_str.trim().parse::<Decimal>().is_ok() {
+            return Err(TempError::MissingUnit(temp_str.trim().to_string()));
+        }
+        let temp = match (self.radix, self.loose) {
+            (Some(radix), _) => Temp::parse_radix(temp_str, radix),
+            (None, true) => Temp::parse_loose(temp_str),
+            (None, false) => Temp::try_parse_with_hint(temp_str),
+        }?;
+        self.scale_input(temp)
+    }
+
+    /// Applies `--scale` to a temperature that was just read.
+    fn scale_input(&self, temp: Temp) -> Result<Temp, TempError> {
+        match self.scale {
+            Some(factor) => temp
+                .checked_scale(factor)
+                .ok_or(TempError::Overflow(temp, temp.unit)),
+            None => Ok(temp),
+        }
+    }
+
+    /// Rounds or pads a result to the decimal places of its original with
+    /// `--match-input-precision`, never rounding below absolute zero with `--strict`.
+    fn match_input_precision(&self, original: Temp, new_temp: Temp) -> Temp {
+        let scale = original.scalar.scale();
+        match (self.match_input_precision, self.strict) {
+            (true, true) => new_temp.rescale_physical(scale),
+            (true, false) => new_temp.rescale(scale),
+            (false, _) => new_temp,
+        }
+    }
+
+    /// The usage error for an argument that couldn't be read. With `--error-format json`, the
+    /// error is written as JSON and the program exits right away instead.
+    fn invalid_input(&self, input: &str, arg: &str, err: TempError) -> clap::Error {
+        let usage_error = invalid_value(input, arg, &err);
+        if self.error_format == ErrorFormat::Json {
+            eprintln!("{}", err.to_json(input));
+            process::exit(usage_error.exit_code());
+        }
+        usage_error
+    }
+
+    /// Passes `temp` through, unless it's too large to convert into every unit, when it's written
+    /// as a failure like [`Unit::fail`].
+    fn convertible(&self, temp: Temp) -> Temp {
+        temp.check_convertible()
+            .unwrap_or_else(|err| self.fail(&temp.to_string(), err))
+    }
+
+    /// Whether any of the flags that change how values are written (and can follow csv-row) were
+    /// given.
+    fn shapes_values(&self) -> bool {
+        self.precision.is_some()
+            || self.round_mode.is_some()
+            || self.scientific
+            || self.trim_zeros
+            || self.no_normalize
+    }
+
+    /// Writes a failed conversion of `input` in the `--error-format`, and exits with an error.
+    fn fail(&self, input: &str, err: TempError) -> ! {
+        eprintln!("{}", err.report(input, self.error_format));
+        process::exit(1);
+    }
+
+    /// How results should be written, from the display flags.
+    fn display_options(&self) -> DisplayOptions {
+        let (precision, max_decimals) = match (self.precision, self.trim_zeros) {
+            (Some(places), true) => (None, Some(places)),
+            (precision, _) => (precision, Some(temp::DEFAULT_MAX_DECIMALS)),
+        };
+        DisplayOptions {
+            precision,
+            max_decimals,
+            keep_trailing_zeros: self.no_normalize || self.match_input_precision,
+            absolute_zero_guard: self.strict,
+            scientific: self.scientific,
+            locale: self.locale,
+            rounding: match (self.round_mode, self.precision) {
+                (Some(mode), _) => mode,
+                (None, Some(_)) => RoundMode::HalfEven,
+                (None, None) => RoundMode::default(),
+            },
+            ..DisplayOptions::default()
+        }
+    }
+}
+
+/// Asks for the unit with a menu, as long as someone is at the terminal to answer it.
+fn select_unit() -> Option<TempUnit> {
+    match io::stdin().is_terminal() {
+        true => menu::prompt(io::stdin().lock(), io::stderr())
+            .ok()
+            .flatten(),
+        false => None,
+    }
+}
+
+fn missing_unit() -> clap::Error {
+    Unit::command().error(
+        ErrorKind::MissingRequiredArgument,
+        "the following required arguments were not provided:\n  <unit>",
+    )
+}
+
+fn invalid_value(value: &str, arg: &str, reason: impl Display) -> clap::Error {
+    Unit::command().error(
+        ErrorKind::ValueValidation,
+        format!("invalid value '{value}' for '{arg}': {reason}"),
+    )
 }
 
 fn main() {
     let args = Unit::parse();
+    let writes_values = matches!(args.command, None | Some(Command::CsvRow { .. }));
+    if !writes_values && args.shapes_values() {
+        Unit::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--precision, --round-mode, --scientific, --trim-zeros and --no-normalize only \
+                 apply to conversions and csv-row",
+            )
+            .exit();
+    }
 
-    let result = match args.new_unit.to_ascii_uppercase() {
-        'C' => Ok(args.original.to_celsius()),
-        'F' => Ok(args.original.to_fahrenheit()),
-        'K' => Ok(args.original.to_kelvin()),
-        _ => Err("Failed: {args.new_unit} is not a valid temperature unit.")
+    match args.command {
+        Some(Command::Compare { first, second }) => compare(
+            args.convertible(first),
+            args.convertible(second),
+            args.tolerance,
+        ),
+        Some(Command::Card {
+            temp,
+            ascii,
+            ref order,
+        }) => println!("{}", card::render(args.convertible(temp), ascii, order)),
+        Some(Command::CsvRow {
+            temp,
+            header,
+            separator,
+            ref order,
+        }) => {
+            let temp = args.convertible(temp);
+            if header {
+                println!("{}", batch::csv_header(order, separator));
+            }
+            println!(
+                "{}",
+                batch::csv_row(temp, order, separator, &args.display_options())
+            );
+        }
+        Some(Command::Classify { temp }) => {
+            match args.convertible(temp).nearest_reference(args.tolerance) {
+                Some((name, reference)) => println!("{temp} is {name} ({reference})"),
+                None => println!("{temp} isn't near any reference temperature"),
+            }
+        }
+        Some(Command::Linspace {
+            start,
+            end,
+            count,
+            unit,
+        }) => {
+            let temps = Temp::linspace(start, end, count)
+                .unwrap_or_else(|err| args.fail(&format!("{start} to {end}"), err));
+            for temp in temps {
+                match temp.checked_convert_to(unit.unwrap_or(start.unit)) {
+                    Ok(temp) => println!("{temp}"),
+                    Err(err) => args.fail(&temp.to_string(), err),
+                }
+            }
+        }
+        Some(Command::FromJson { file, path, unit }) => {
+            let document = fs::read_to_string(&file)
+                .map_err(|err| format!("Unable to read {}: {err}", file.display()))
+                .and_then(|text| {
+                    serde_json::from_str(&text)
+                        .map_err(|err| format!("{} isn't valid JSON: {err}", file.display()))
+                });
+            let converted = document
+                .and_then(|document| batch::temp_from_json(&document, &path))
+                .and_then(|temp| {
+                    let new_temp = temp
+                        .checked_convert_to(unit)
+                        .map_err(|err| err.to_string())?;
+                    Ok((temp, new_temp))
+                });
+            match converted {
+                Ok((temp, new_temp)) => println!(
+                    "{}",
+                    Template::default().render(temp, new_temp, &DisplayOptions::default())
+                ),
+                Err(msg) => {
+                    eprintln!("{msg}");
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Watch {
+            path,
+            unit,
+            interval,
+        }) => watch(path, unit, Duration::from_millis(interval)),
+        Some(Command::Diff {
+            first,
+            second,
+            unit,
+        }) => match first
+            .checked_diff(second)
+            .and_then(|delta| delta.checked_format_in(unit.unwrap_or(first.unit)))
+        {
+            Ok(delta) => println!("{first} - {second} = {delta}"),
+            Err(err) => args.fail(&format!("{first} - {second}"), err),
+        },
+        Some(Command::Gradient {
+            from,
+            to,
+            step,
+            plain,
+        }) => {
+            let color =
+                !plain && io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            let (from, to) = (args.convertible(from), args.convertible(to));
+            match gradient::render(from, to, TempDelta::of(step.scalar, step.unit), color) {
+                Ok(strip) => println!("{strip}"),
+                Err(msg) => {
+                    eprintln!("{msg}");
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Phase { temp }) => println!("{}", args.convertible(temp).water_phase()),
+        Some(Command::Describe { temp }) => {
+            println!("{temp} is {}", args.convertible(temp).describe())
+        }
+        Some(Command::Reverse { value, to }) => {
+            for (reading, result) in Temp::guess_units(value, to) {
+                match reading.check_physical() {
+                    Ok(_) => println!("{reading} is equal to {result}"),
+                    Err(_) => println!("{reading} is equal to {result} (below absolute zero)"),
+                }
+            }
+        }
+        Some(Command::Coeff { value, per, to }) => {
+            let coefficient = TempCoefficient { value, per };
+            match coefficient.checked_convert_to(to) {
+                Ok(converted) => println!("{coefficient} is equal to {converted}"),
+                Err(err) => args.fail(&coefficient.to_string(), err),
+            }
+        }
+        Some(Command::Convert {
+            stdin_json_lines: true,
+            ..
+        }) => {
+            if let Err(err) = batch::convert_json_lines(io::stdin().lock(), io::stdout().lock()) {
+                eprintln!("Unable to convert the stream: {err}");
+                process::exit(1);
+            }
+        }
+        Some(Command::Convert { .. }) => match batch::convert_json_requests(io::stdin().lock()) {
+            Ok(results) => println!("{results}"),
+            Err(msg) => {
+                eprintln!("{msg}");
+                process::exit(1);
+            }
+        },
+        Some(Command::Rate {
+            change,
+            ref per,
+            to,
+        }) => {
+            let shown = TempDelta::checked_of(change.scalar, change.unit).and_then(|delta| {
+                let rate = TempRate {
+                    delta,
+                    per: per.clone(),
+                };
+                Ok((
+                    rate.checked_format_in(change.unit)?,
+                    rate.checked_format_in(to)?,
+                ))
+            });
+            match shown {
+                Ok((given, restated)) => println!("{given} is equal to {restated}"),
+                Err(err) => args.fail(&change.to_string(), err),
+            }
+        }
+        Some(Command::Sort {
+            ref temps,
+            to,
+            desc,
+        }) => {
+            let mut temps: Vec<Temp> = temps.iter().map(|temp| args.convertible(*temp)).collect();
+            temp::sort_temps(&mut temps, desc);
+            for temp in temps {
+                match to.map_or(Ok(temp), |unit| temp.checked_convert_to(unit)) {
+                    Ok(temp) => println!("{temp}"),
+                    Err(err) => args.fail(&temp.to_string(), err),
+                }
+            }
+        }
+        Some(Command::SelfTest) => self_test(args.tolerance),
+        Some(Command::RoundTrip { temp }) => {
+            let trips = cfk::self_test::round_trips(args.convertible(temp), args.tolerance);
+            println!("{}", cfk::self_test::round_trip_table(&trips));
+            if trips.iter().any(|trip| !trip.passed) {
+                process::exit(1);
+            }
+        }
+        Some(Command::Info) => println!("{}", info::render()),
+        #[cfg(feature = "schema")]
+        Some(Command::Schema) => println!("{:#}", temp::json_schema()),
+        Some(Command::Repl) => match Repl::new(repl::default_history_path()) {
+            Ok(mut repl) => repl.run(),
+            Err(err) => exit_with(format!("Unable to start the REPL: {err}")),
+        },
+        None => convert(&args),
+    }
+}
+
+fn compare(first: Temp, second: Temp, tolerance: Decimal) {
+    match first.cmp_physical(second, tolerance) {
+        Ordering::Less => println!("{first} is colder than {second}"),
+        Ordering::Equal => println!("{first} is equal to {second}"),
+        Ordering::Greater => println!("{first} is hotter than {second}"),
+    }
+}
+
+fn watch(path: PathBuf, unit: TempUnit, interval: Duration) {
+    let mut tail = Tail::new(&path);
+    loop {
+        match tail.poll() {
+            Ok(lines) => {
+                for line in lines.iter().map(|line| line.trim()) {
+                    let converted = line
+                        .parse::<Temp>()
+                        .and_then(|temp| Ok((temp, temp.checked_convert_to(unit)?)));
+                    match converted {
+                        Ok((temp, new_temp)) => println!(
+                            "{}",
+                            Template::default().render(temp, new_temp, &DisplayOptions::default())
+                        ),
+                        Err(_) if line.is_empty() => {}
+                        Err(err) => eprintln!("Unable to convert {line}: {err}"),
+                    }
+                }
+            }
+            Err(err) => exit_with(format!("Unable to read {}: {err}", path.display())),
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn self_test(tolerance: Decimal) {
+    let checks = cfk::self_test::run(tolerance);
+    for check in &checks {
+        println!("{check}");
+    }
+    if checks.iter().any(|check| !check.passed) {
+        std::process::exit(1);
+    }
+}
+
+fn convert(args: &Unit) {
+    if let (Some(path), Some(original), Some(new_unit)) =
+        (&args.units_file, &args.original, &args.new_unit)
+    {
+        let units = CustomUnits::load(path).unwrap_or_else(|msg| {
+            Unit::command()
+                .error(ErrorKind::ValueValidation, msg)
+                .exit()
+        });
+        if units.parse_reading(original).is_some() || units.get(new_unit).is_some() {
+            let reshaped = args.snap.is_some() || args.whole || args.sensor.is_some();
+            if reshaped || args.pipe || args.template.is_some() {
+                Unit::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "--snap, --whole, --sensor, --pipe and --template can't be used with a \
+                         unit from --units-file",
+                    )
+                    .exit();
+            }
+            match convert_custom(args, &units, original, new_unit) {
+                Ok(line) => println!("{line}"),
+                Err(err) => args.fail(original, err),
+            }
+            return;
+        }
+    }
+
+    if args.dry_run {
+        return dry_run(args);
+    }
+    if args.group_by_unit {
+        return group_by_unit(args);
+    }
+    let (original, new_unit) = match args.unit_from_stdin {
+        true => match batch::read_unit_line(&mut io::stdin().lock()) {
+            Ok(unit) => (None, unit),
+            Err(msg) => Unit::command()
+                .error(ErrorKind::ValueValidation, msg)
+                .exit(),
+        },
+        false => args.positionals().unwrap_or_else(|err| err.exit()),
+    };
+    if original.is_some() && args.from_file.is_some() {
+        Unit::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "a temperature can't be given along with --from-file",
+            )
+            .exit();
+    }
+    let convert = |temp: Temp| {
+        let temp = temp.check_range(args.min, args.max, args.tolerance)?;
+        let new_temp = match (args.strict, args.base_unit) {
+            (true, _) => temp.try_convert_to(new_unit)?,
+            (false, true) => temp.checked_convert_to(TempUnit::BASE)?,
+            (false, false) => temp.checked_convert_to(new_unit)?,
+        };
+        if args.require_exact {
+            temp.try_convert_exact(new_unit)?;
+        }
+        if args.round_trip_check {
+            let back = new_temp.checked_convert_to(temp.unit)?;
+            if !temp.approx_eq(back, args.tolerance) {
+                eprintln!(
+                    "Warning: converting {temp} to {new_unit} and back drifts by {} {}",
+                    (back.scalar - temp.scalar).normalize(),
+                    temp.unit
+                );
+            }
+        }
+        let new_temp = match args.snap {
+            Some(step) => new_temp.snap_to(step)?,
+            None if args.whole => new_temp
+                .round_to_integer_with(args.round_mode.unwrap_or(RoundMode::HalfEven).into()),
+            None => match args.sensor {
+                Some(resolution) => resolution.quantize(new_temp)?,
+                None => new_temp,
+            },
+        };
+        Ok(args.match_input_precision(temp, new_temp))
+    };
+    let template = match args.pipe {
+        true => Template::pipe(args.pipe_unit),
+        false => args.template.clone().unwrap_or_default(),
+    };
+    let template = match args.annotate_drift {
+        true => template.with_drift_note(),
+        false => template,
+    }
+    .wrapped(&args.output_prefix, &args.output_suffix);
+
+    if let (Some(original), true) = (original, args.as_equation || args.explain_json) {
+        let shown = original
+            .check_range(args.min, args.max, args.tolerance)
+            .and_then(|temp| match args.strict {
+                true => temp.check_physical(),
+                false => Ok(temp),
+            })
+            .and_then(|temp| match args.explain_json {
+                true => temp
+                    .explain_json(new_unit)
+                    .map(|explained| explained.to_string()),
+                false => temp
+                    .checked_convert_to(new_unit)
+                    .map(|_| temp.to_equation(new_unit)),
+            });
+        match shown {
+            Ok(shown) => return println!("{shown}"),
+            Err(err) => args.fail(&original.to_string(), err),
+        }
+    }
+    if let (Some(original), Some(repeat)) = (original, args.repeat) {
+        let start = Instant::now();
+        for _ in 0..repeat {
+            let _ = hint::black_box(convert(hint::black_box(original)));
+        }
+        let total = start.elapsed();
+        eprintln!(
+            "Converted {repeat} times in {total:?} ({:?} each)",
+            total / repeat
+        );
+    }
+    match original {
+        Some(original) => match convert(original) {
+            Ok(new_temp) => {
+                let result = template.render(original, new_temp, &args.display_options());
+                match args.emoji && !args.pipe {
+                    true => println!("{} {result}", new_temp.emoji()),
+                    false => println!("{result}"),
+                }
+            }
+            Err(err) => args.fail(args.original.as_deref().unwrap_or_default(), err),
+        },
+        None => convert_batch(args, new_unit, convert, &template),
+    }
+}
+
+/// Converts between units where at least one is a scale from the units file.
+fn convert_custom(
+    args: &Unit,
+    units: &CustomUnits,
+    original: &str,
+    new_unit: &str,
+) -> Result<String, TempError> {
+    let options = args.display_options();
+    let (temp, written) = match units.parse_reading(original) {
+        Some((reading, scale)) => (
+            scale.to_temp(reading),
+            scale.format_reading(reading, &options),
+        ),
+        None => {
+            let temp = args.parse_temp(original)?;
+            (temp, temp.format_with(&options))
+        }
+    };
+    let temp = temp.check_range(args.min, args.max, args.tolerance)?;
+    if args.strict {
+        temp.check_physical()?;
+    }
+    let converted = match units.get(new_unit) {
+        Some(scale) => scale.format_reading(scale.from_temp(temp), &options),
+        None => temp
+            .checked_convert_to(TempUnit::parse(new_unit)?)?
+            .format_with(&options),
     };
+    Ok(format!("{written} is equal to {converted}"))
+}
+
+/// Writes `msg` to stderr, and exits with an error.
+fn exit_with(msg: impl Display) -> ! {
+    eprintln!("{msg}");
+    process::exit(1);
+}
 
-    match result {
-        Ok(new_temp) => println!("{} is equal to {}", args.original, new_temp),
-        Err(msg) => eprintln!("{msg}")
+/// Reads values from `--from-file`, or stdin when no file is given, with `--utf8-lossy` applied.
+fn batch_source(args: &Unit) -> Result<Box<dyn BufRead>, String> {
+    let source: Box<dyn BufRead> = match &args.from_file {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(err) => return Err(format!("Unable to read {}: {err}", path.display())),
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+    match args.utf8_lossy {
+        true => Ok(Box::new(batch::LossyUtf8::new(source))),
+        false => Ok(source),
+    }
+}
+
+/// Where results of values from stdin or a file go: `--to-file`, or stdout when no file is given.
+fn batch_output(args: &Unit) -> Result<Box<dyn Write>, String> {
+    match &args.to_file {
+        Some(path) => match File::create(path) {
+            Ok(file) => Ok(Box::new(file)),
+            Err(err) => Err(format!("Unable to write to {}: {err}", path.display())),
+        },
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// How each value from stdin or a file is read, assuming the unit in the file's name with
+/// `--unit-from-filename`.
+fn batch_parser(args: &Unit) -> impl Fn(&str) -> Result<Temp, TempError> + Sync + '_ {
+    let assumed = match (&args.from_file, args.unit_from_filename) {
+        (Some(path), true) if !args.require_unit => match batch::unit_from_filename(path) {
+            Ok(unit) => Some(unit),
+            Err(msg) => Unit::command()
+                .error(ErrorKind::ValueValidation, msg)
+                .exit(),
+        },
+        _ => None,
+    };
+    move |input: &str| {
+        let input = match args.input_column {
+            Some(column) => batch::column(input, column as usize)?,
+            None => input,
+        };
+        match assumed {
+            Some(unit) => Temp::parse_assuming(input, unit)
+                .or_else(|err| match args.loose {
+                    true => Temp::parse_loose(input),
+                    false => Err(err),
+                })
+                .and_then(|temp| args.scale_input(temp)),
+            None => args.parse_temp(input),
+        }
+    }
+}
+
+/// Summarizes the values from stdin or a file by the unit each was written in, for
+/// `--group-by-unit`.
+fn group_by_unit(args: &Unit) {
+    let groups = batch_source(args).and_then(|source| {
+        stats::read_stats_by_unit(source, batch_parser(args), io::stderr().lock())
+            .map_err(|err| format!("Unable to read input: {err}"))
+    });
+    let groups = match groups {
+        Ok(groups) if groups.is_empty() => exit_with("No temperatures were read"),
+        Ok(groups) => groups,
+        Err(msg) => exit_with(msg),
+    };
+    let mut out = match batch_output(args) {
+        Ok(out) => out,
+        Err(msg) => exit_with(msg),
+    };
+    for (unit, stats) in groups {
+        match stats.in_unit(unit) {
+            Ok(Some(summary)) => {
+                if let Err(err) = writeln!(out, "{unit}: {summary}") {
+                    exit_with(format!("Unable to write results: {err}"));
+                }
+            }
+            Ok(None) => {}
+            Err(err) => exit_with(err),
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Reads every value from stdin or a file for `--dry-run`, reporting which couldn't be read.
+fn dry_run(args: &Unit) {
+    let entries =
+        batch_source(args).and_then(|source| batch::read_entries(args.input_format, source));
+    let records = match entries {
+        Ok(entries) => batch::convert_entries(entries, batch_parser(args), Ok),
+        Err(msg) => {
+            eprintln!("{msg}");
+            process::exit(1);
+        }
+    };
+    let tally = Tally::of(&records);
+    eprintln!(
+        "valid {}, invalid {}, total {}",
+        tally.converted,
+        tally.failed,
+        records.len()
+    );
+    if tally.failed > 0 {
+        let positions: Vec<String> = batch::failed_positions(&records)
+            .iter()
+            .map(|position| position.to_string())
+            .collect();
+        let kind = match args.input_format {
+            InputFormat::Json => "positions",
+            InputFormat::Lines | InputFormat::Csv => "lines",
+        };
+        eprintln!("Invalid {kind}: {}", positions.join(", "));
+        process::exit(1);
+    }
+}
+
+fn convert_batch(
+    args: &Unit,
+    new_unit: TempUnit,
+    convert: impl Fn(Temp) -> Result<Temp, TempError> + Sync,
+    template: &Template,
+) {
+    let format = match args.pipe {
+        true => OutputFormat::Lines,
+        false => args.output_format.unwrap_or(args.input_format.into()),
+    };
+    if args.separator.is_some() && format != OutputFormat::Lines {
+        Unit::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--separator only applies to results written one per line, and JSON and CSV \
+                 input is written back in its own format unless --output-format lines is given",
+            )
+            .exit();
+    }
+    let source = match batch_source(args) {
+        Ok(source) => source,
+        Err(msg) => exit_with(msg),
+    };
+    let parse = batch_parser(args);
+    let mut out = match batch_output(args) {
+        Ok(out) => out,
+        Err(msg) => exit_with(msg),
+    };
+
+    if args.batch_stats {
+        let stats = stats::read_stats(source, |line| convert(parse(line)?), io::stderr().lock());
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(err) => exit_with(format!("Unable to read input: {err}")),
+        };
+        match stats.in_unit(new_unit) {
+            Ok(Some(summary)) => {
+                if let Err(err) = writeln!(out, "{summary}") {
+                    exit_with(format!("Unable to write results: {err}"));
+                }
+            }
+            Ok(None) => exit_with("No temperatures were read"),
+            Err(err) => exit_with(err),
+        }
+        return;
+    }
+
+    // Results are written as they're converted, unless something needs every one of them first
+    let streamed = !(args.in_place
+        || args.parallel
+        || args.count_only
+        || args.align_output
+        || args.input_format == InputFormat::Json
+        || (args.interactive_fix && io::stdin().is_terminal()));
+    let streams_lines = format == OutputFormat::Lines && args.separator.is_none();
+    if streamed && (format == OutputFormat::Ndjson || streams_lines) {
+        let options = args.display_options();
+        let tally = batch::convert_streamed(
+            args.input_format,
+            source,
+            args.preview,
+            args.fail_fast,
+            &parse,
+            &convert,
+            |record| match format {
+                OutputFormat::Ndjson => batch::write_ndjson_record(record, &options, &mut out),
+                _ => batch::write_line_record(
+                    record,
+                    template,
+                    &options,
+                    args.error_format,
+                    &mut out,
+                    io::stderr().lock(),
+                ),
+            },
+        );
+        return report_tally(args, tally.unwrap_or_else(|msg| exit_with(msg)));
+    }
+
+    let convert_all = |entries| match args.parallel {
+        true => batch::convert_entries_parallel(entries, &parse, &convert),
+        false => batch::convert_entries(entries, &parse, &convert),
+    };
+    // In place, every line is written back, so the untrimmed lines are kept to rewrite
+    let mut lines = Vec::new();
+    let records = match (args.in_place, args.fail_fast, args.preview) {
+        (true, ..) => batch::read_lines(source).map(|read| {
+            lines = read;
+            let entries = lines
+                .iter()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .cloned()
+                .collect();
+            convert_all(entries)
+        }),
+        (false, true, limit) => {
+            batch::convert_until_failure(args.input_format, source, limit, &parse, &convert)
+        }
+        (false, false, Some(limit)) => {
+            batch::read_first_entries(args.input_format, source, limit).map(convert_all)
+        }
+        (false, false, None) => batch::read_entries(args.input_format, source).map(convert_all),
+    };
+    let mut records = match records {
+        Ok(records) => records,
+        Err(msg) => exit_with(msg),
+    };
+    if args.interactive_fix && io::stdin().is_terminal() {
+        batch::fix_units(
+            &mut records,
+            |entry, err| {
+                menu::prompt_fix(io::stdin().lock(), io::stderr(), entry, err)
+                    .ok()
+                    .flatten()
+            },
+            |temp| convert(args.scale_input(temp)?),
+        );
+    }
+    let records = &records[..];
+    if args.count_only {
+        let tally = Tally::of(records);
+        if let Err(err) = writeln!(out, "{}", tally.counts()) {
+            exit_with(format!("Unable to write results: {err}"));
+        }
+        if tally.failed > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+    let options = args.display_options();
+    let aligned;
+    let template = match args.align_output {
+        true => {
+            let conversions: Vec<(Temp, Temp)> = records
+                .iter()
+                .filter_map(|record| record.result.clone().ok())
+                .collect();
+            aligned = template.aligned_to(&conversions, &options);
+            &aligned
+        }
+        false => template,
+    };
+    let written = match (format, &args.separator) {
+        _ if args.in_place => batch::write_in_place(
+            &lines,
+            records,
+            args.input_column.unwrap_or(1) as usize,
+            &options,
+            args.error_format,
+            out,
+            io::stderr().lock(),
+        ),
+        (OutputFormat::Lines, Some(separator)) => batch::write_joined(
+            records,
+            template,
+            &options,
+            separator,
+            args.error_format,
+            out,
+            io::stderr().lock(),
+        ),
+        _ => batch::write_records(
+            format,
+            records,
+            template,
+            &options,
+            args.error_format,
+            out,
+            io::stderr().lock(),
+        ),
+    };
+    if let Err(err) = written {
+        exit_with(format!("Unable to write results: {err}"));
+    }
+    report_tally(args, Tally::of(records));
+}
+
+/// Writes the tally of a batch with `--summary` (or when reading or writing a file), and exits
+/// with an error if anything failed.
+fn report_tally(args: &Unit, tally: Tally) {
+    if args.summary || args.from_file.is_some() || args.to_file.is_some() {
+        eprintln!("{tally}");
+    }
+    if tally.failed > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Unit;
+    use cfk::batch::{self, InputFormat};
+    use cfk::error::TempError;
+    use cfk::temp::{Temp, TempUnit};
+    use clap::error::ErrorKind;
+    use clap::Parser;
+
+    fn positionals(args: &[&str]) -> Result<(Option<Temp>, TempUnit), clap::Error> {
+        Unit::try_parse_from([&["cfk"], args].concat())?.positionals()
+    }
+
+    #[test]
+    fn test_unit_as_flag_or_positional() -> Result<(), Box<dyn std::error::Error>> {
+        let boiling = Some("100C".parse::<Temp>()?);
+        assert_eq!(
+            positionals(&["100C", "F"])?,
+            (boiling, TempUnit::FAHRENHEIT)
+        );
+        assert_eq!(
+            positionals(&["100C", "--to", "F"])?,
+            (boiling, TempUnit::FAHRENHEIT)
+        );
+        assert_eq!(
+            positionals(&["-t", "kelvin", "100C"])?,
+            (boiling, TempUnit::KELVIN)
+        );
+        assert_eq!(positionals(&["--to", "K"])?, (None, TempUnit::KELVIN));
+        assert_eq!(positionals(&["K"])?, (None, TempUnit::KELVIN));
+        assert_eq!(
+            positionals(&["--scale", "0.1", "985F", "C"])?,
+            (Some("98.5F".parse()?), TempUnit::CELSIUS)
+        );
+        assert_eq!(
+            positionals(&["100C", "F", "--to", "K"]).map_err(|err| err.kind()),
+            Err(ErrorKind::ArgumentConflict)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_precision_keeps_zeros() -> Result<(), Box<dyn std::error::Error>> {
+        let shown = |args: &[&str]| -> Result<String, Box<dyn std::error::Error>> {
+            let options = Unit::try_parse_from([&["cfk"], args].concat())?.display_options();
+            let (whole, fraction) = ("50C".parse::<Temp>()?, "37.7777C".parse::<Temp>()?);
+            Ok(format!(
+                "{} {}",
+                whole.format_with(&options),
+                fraction.format_with(&options)
+            ))
+        };
+        assert_eq!(shown(&["--precision", "2", "F"])?, "50.00 C 37.78 C");
+        assert_eq!(shown(&["--precision", "0", "F"])?, "50 C 38 C");
+        assert_eq!(
+            shown(&["--precision", "2", "--trim-zeros", "F"])?,
+            "50 C 37.78 C"
+        );
+        assert_eq!(shown(&["F"])?, "50 C 37.7777 C");
+        Ok(())
+    }
+
+    #[test]
+    fn test_require_unit() -> Result<(), Box<dyn std::error::Error>> {
+        let strict = Unit::try_parse_from(["cfk", "--require-unit", "F"])?;
+        assert_eq!(
+            strict.parse_temp(" 100 "),
+            Err(TempError::MissingUnit("100".to_string()))
+        );
+        assert_eq!(strict.parse_temp("100C")?, "100C".parse::<Temp>()?);
+        assert_eq!(
+            positionals(&["--require-unit", "100", "F"]).map_err(|err| err.kind()),
+            Err(ErrorKind::ValueValidation)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_base_unit() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(positionals(&["--base-unit"])?, (None, TempUnit::KELVIN));
+        assert_eq!(
+            positionals(&["--base-unit", "100C"])?,
+            (Some("100C".parse()?), TempUnit::KELVIN)
+        );
+        assert_eq!(
+            positionals(&["--base-unit", "100C", "F"]).map_err(|err| err.kind()),
+            Err(ErrorKind::ArgumentConflict)
+        );
+
+        let entries = batch::read_entries(InputFormat::Lines, "0C\n32F\n300K\n".as_bytes())?;
+        let records =
+            batch::convert_entries(entries, str::parse, |temp: Temp| Ok(temp.to_base_unit()));
+        let units: Vec<TempUnit> = records
+            .iter()
+            .filter_map(|record| record.result.as_ref().ok())
+            .map(|(_, converted)| converted.unit)
+            .collect();
+        assert_eq!(units, [TempUnit::BASE; 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_input_precision() -> Result<(), Box<dyn std::error::Error>> {
+        let shown = |args: &[&str], original: &str| -> Result<String, Box<dyn std::error::Error>> {
+            let args = Unit::try_parse_from([&["cfk"], args].concat())?;
+            let original = original.parse::<Temp>()?;
+            let new_temp = args.match_input_precision(original, original.to_fahrenheit());
+            Ok(new_temp.format_with(&args.display_options()))
+        };
+        assert_eq!(
+            shown(&["--match-input-precision", "F"], "100.0C")?,
+            "212.0 F"
+        );
+        assert_eq!(
+            shown(&["--match-input-precision", "F"], "37.00C")?,
+            "98.60 F"
+        );
+        assert_eq!(shown(&["--match-input-precision", "F"], "-17.8C")?, "0.0 F");
+        assert_eq!(shown(&["F"], "100.0C")?, "212 F");
+        Ok(())
+    }
+
+    #[test]
+    fn test_precision_rounds_half_to_even() -> Result<(), Box<dyn std::error::Error>> {
+        let shown = |args: &[&str]| -> Result<String, Box<dyn std::error::Error>> {
+            let options = Unit::try_parse_from([&["cfk"], args].concat())?.display_options();
+            Ok(["0.125C", "0.135C", "12.5C"]
+                .map(|temp_str| {
+                    temp_str
+                        .parse::<Temp>()
+                        .map(|temp| temp.format_with(&options))
+                })
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", "))
+        };
+        assert_eq!(
+            shown(&["--precision", "2", "F"])?,
+            "0.12 C, 0.14 C, 12.50 C"
+        );
+        assert_eq!(shown(&["--precision", "0", "F"])?, "0 C, 0 C, 12 C");
+        assert_eq!(
+            shown(&[
+                "--precision",
+                "2",
+                "--round-mode",
+                "half-away-from-zero",
+                "F"
+            ])?,
+            "0.13 C, 0.14 C, 12.50 C"
+        );
+        assert_eq!(shown(&["F"])?, "0.125 C, 0.135 C, 12.5 C");
+        Ok(())
+    }
+}
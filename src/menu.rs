@@ -0,0 +1,102 @@
+use std::io::{self, BufRead, Write};
+
+use crate::error::TempError;
+use crate::temp::TempUnit;
+
+/// The units offered by [`prompt`], in menu order
+pub const CHOICES: [TempUnit; 3] = [TempUnit::CELSIUS, TempUnit::FAHRENHEIT, TempUnit::KELVIN];
+
+/// The numbered menu line (ex. `1) Celsius  2) Fahrenheit  3) Kelvin`).
+pub fn menu() -> String {
+    CHOICES
+        .iter()
+        .enumerate()
+        .map(|(index, unit)| format!("{}) {}", index + 1, unit.full_name()))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Reads a menu answer: the number of a choice, or any unit spelling [`TempUnit::parse`] accepts.
+pub fn select(answer: &str) -> Option<TempUnit> {
+    match answer.trim().parse::<usize>() {
+        Ok(number) => number
+            .checked_sub(1)
+            .and_then(|index| CHOICES.get(index).copied()),
+        Err(_) => TempUnit::parse(answer).ok(),
+    }
+}
+
+/// Shows the menu on `output` and asks until a valid answer is read from `input`. Returns `None`
+/// if the input ends first.
+pub fn prompt(mut input: impl BufRead, mut output: impl Write) -> io::Result<Option<TempUnit>> {
+    writeln!(output, "Convert into which unit?\n{}", menu())?;
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(None);
+        }
+        match select(&answer) {
+            Some(unit) => return Ok(Some(unit)),
+            None => writeln!(output, "{} isn't one of the choices", answer.trim())?,
+        }
+    }
+}
+
+/// Asks which unit `entry`, which couldn't be read because of `err`, is really in. Like
+/// [`prompt`], but an empty answer or the end of `input` gives `None`, to skip the entry.
+pub fn prompt_fix(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    entry: &str,
+    err: &TempError,
+) -> io::Result<Option<TempUnit>> {
+    writeln!(
+        output,
+        "{entry}: {err}\nWhich unit is it in? Leave blank to skip it\n{}",
+        menu()
+    )?;
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 || answer.trim().is_empty() {
+            return Ok(None);
+        }
+        match select(&answer) {
+            Some(unit) => return Ok(Some(unit)),
+            None => writeln!(output, "{} isn't one of the choices", answer.trim())?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{menu, prompt, select};
+    use crate::temp::TempUnit;
+
+    #[test]
+    fn test_select() {
+        assert_eq!(menu(), "1) Celsius  2) Fahrenheit  3) Kelvin");
+        assert_eq!(select("1"), Some(TempUnit::CELSIUS));
+        assert_eq!(select(" 2\n"), Some(TempUnit::FAHRENHEIT));
+        assert_eq!(select("3"), Some(TempUnit::KELVIN));
+        assert_eq!(select("kelvin"), Some(TempUnit::KELVIN));
+        for answer in ["0", "4", "-1", "", "x"] {
+            assert_eq!(select(answer), None, "{answer}");
+        }
+    }
+
+    #[test]
+    fn test_prompt_retries() -> std::io::Result<()> {
+        let mut output = Vec::new();
+        assert_eq!(
+            prompt("9\n2\n".as_bytes(), &mut output)?,
+            Some(TempUnit::FAHRENHEIT)
+        );
+        assert!(String::from_utf8_lossy(&output).contains("9 isn't one of the choices"));
+        assert_eq!(prompt("".as_bytes(), std::io::sink())?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::temp::TempUnit;
+
+/// Countries (by ISO 3166 code) that still give everyday temperatures in Fahrenheit. Everywhere
+/// else uses Celsius.
+pub const FAHRENHEIT_COUNTRIES: [&str; 13] = [
+    "US", "AS", "GU", "MP", "PR", "VI", "BS", "BZ", "KY", "LR", "PW", "FM", "MH",
+];
+
+/// Every country's ISO 3166 code, for telling a country apart from a typo.
+const COUNTRIES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// The environment variables a country is detected from, in the order POSIX gives them
+/// precedence.
+pub const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_MEASUREMENT", "LANG"];
+
+/// The unit people in a country expect temperatures in, from its two-letter code in any case.
+/// A code that isn't one of ISO 3166's gives `None`.
+pub fn unit_for_country(country: &str) -> Option<TempUnit> {
+    let is = |code: &&str| code.eq_ignore_ascii_case(country.trim());
+    match (
+        COUNTRIES.iter().any(is),
+        FAHRENHEIT_COUNTRIES.iter().any(is),
+    ) {
+        (false, _) => None,
+        (true, true) => Some(TempUnit::FAHRENHEIT),
+        (true, false) => Some(TempUnit::CELSIUS),
+    }
+}
+
+/// Reads the country out of a POSIX locale name (ex. US from en_US.UTF-8). Locales without a
+/// country, like C or POSIX, give `None`.
+pub fn country_from_locale(locale: &str) -> Option<&str> {
+    let name = locale.split(['.', '@']).next()?;
+    let (_, country) = name.split_once(['_', '-'])?;
+    (country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic())).then_some(country)
+}
+
+/// Works out the user's country from the first of [`LOCALE_VARS`] that is set (and not empty).
+/// As with POSIX, only that one is looked at: if it doesn't name a country (ex. LC_ALL=C), the
+/// result is `None` even when a later one does. The code found isn't checked against ISO 3166.
+pub fn detect_country() -> Option<String> {
+    LOCALE_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .and_then(|locale| country_from_locale(&locale).map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{country_from_locale, unit_for_country, COUNTRIES, FAHRENHEIT_COUNTRIES};
+    use crate::temp::TempUnit;
+
+    #[test]
+    fn test_unit_for_country() {
+        assert_eq!(unit_for_country("US"), Some(TempUnit::FAHRENHEIT));
+        assert_eq!(unit_for_country("us"), Some(TempUnit::FAHRENHEIT));
+        assert_eq!(unit_for_country("DE"), Some(TempUnit::CELSIUS));
+        assert_eq!(unit_for_country("CA"), Some(TempUnit::CELSIUS));
+        assert_eq!(unit_for_country("ZZ"), None);
+        assert_eq!(unit_for_country("USA"), None);
+        assert!(FAHRENHEIT_COUNTRIES
+            .iter()
+            .all(|code| COUNTRIES.contains(code)));
+    }
+
+    #[test]
+    fn test_country_from_locale() {
+        assert_eq!(country_from_locale("en_US.UTF-8"), Some("US"));
+        assert_eq!(country_from_locale("de_DE@euro"), Some("DE"));
+        assert_eq!(country_from_locale("fr-CA"), Some("CA"));
+        assert_eq!(country_from_locale("C"), None);
+        assert_eq!(country_from_locale("POSIX"), None);
+        assert_eq!(country_from_locale("C.UTF-8"), None);
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::temp::{DisplayOptions, Temp, TempUnit};
+use crate::template::Template;
+
+/// Where the REPL keeps its history between sessions: `.cfk_history` in the home directory.
+pub fn default_history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".cfk_history"))
+}
+
+/// An interactive prompt that converts lines like `100C F`
+pub struct Repl {
+    editor: DefaultEditor,
+    history_path: Option<PathBuf>,
+}
+
+impl Repl {
+    /// Starts a REPL, loading any earlier history from `history_path`.
+    pub fn new(history_path: Option<PathBuf>) -> rustyline::Result<Self> {
+        let mut editor = DefaultEditor::new()?;
+        if let Some(path) = &history_path {
+            // A missing history file just means this is the first session
+            let _ = editor.load_history(path);
+        }
+
+        Ok(Repl {
+            editor,
+            history_path,
+        })
+    }
+
+    /// Reads and answers lines until the user quits.
+    pub fn run(&mut self) {
+        loop {
+            match self.editor.readline("cfk> ") {
+                Ok(line) => match self.handle(&line) {
+                    Some(reply) if reply.is_empty() => {}
+                    Some(reply) => println!("{reply}"),
+                    None => break,
+                },
+                Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+                Err(err) => {
+                    eprintln!("Unable to read input: {err}");
+                    break;
+                }
+            }
+        }
+        self.save_history();
+    }
+
+    /// Answers a single line, or returns `None` if it asks to quit. Each conversion, successful
+    /// or not, is added to the history.
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+        match line {
+            "" => Some(String::new()),
+            "quit" | "exit" => None,
+            "history" => Some(
+                self.editor
+                    .history()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, entry)| format!("{:>4}  {entry}", index + 1))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => {
+                let _ = self.editor.add_history_entry(line);
+                Some(convert_line(line))
+            }
+        }
+    }
+
+    fn save_history(&mut self) {
+        if let Some(path) = &self.history_path {
+            if let Err(err) = self.editor.save_history(path) {
+                eprintln!("Unable to save history to {}: {err}", path.display());
+            }
+        }
+    }
+}
+
+fn convert_line(line: &str) -> String {
+    let Some((temp_str, unit_str)) = line.rsplit_once(char::is_whitespace) else {
+        return format!("Expected a temperature and a unit (ex. 100C F), not {line}");
+    };
+    let converted = temp_str.parse::<Temp>().and_then(|original| {
+        let unit = TempUnit::parse(unit_str)?;
+        Ok((original, original.checked_convert_to(unit)?))
+    });
+    match converted {
+        Ok((original, new_temp)) => {
+            Template::default().render(original, new_temp, &DisplayOptions::default())
+        }
+        Err(err) => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Repl;
+
+    #[test]
+    fn test_history_is_recorded() -> rustyline::Result<()> {
+        let mut repl = Repl::new(None)?;
+        assert_eq!(
+            repl.handle("100C F"),
+            Some("100 C is equal to 212 F".to_string())
+        );
+        assert_eq!(
+            repl.handle("15d K"),
+            Some("d is not a valid temperature unit".to_string())
+        );
+        assert_eq!(repl.handle(""), Some(String::new()));
+        assert_eq!(
+            repl.handle("history"),
+            Some("   1  100C F\n   2  15d K".to_string())
+        );
+        assert_eq!(
+            repl.handle("79228162514264337593543950335C F"),
+            Some("79228162514264337593543950335 C is too large to convert to F".to_string())
+        );
+        assert_eq!(repl.handle("quit"), None);
+        Ok(())
+    }
+}
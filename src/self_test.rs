@@ -0,0 +1,207 @@
+use std::fmt::{Display, Formatter};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::temp::{DisplayOptions, Temp, TempDelta, TempUnit};
+
+/// Well-known temperatures, written out in every unit
+const REFERENCES: [(&str, [Decimal; 3]); 4] = [
+    ("absolute zero", [dec!(-273.15), dec!(-459.67), dec!(0)]),
+    ("water freezes", [dec!(0), dec!(32), dec!(273.15)]),
+    ("body temperature", [dec!(37), dec!(98.6), dec!(310.15)]),
+    ("water boils", [dec!(100), dec!(212), dec!(373.15)]),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of converting one reference value into one unit
+pub struct Check {
+    pub name: &'static str,
+    pub original: Temp,
+    pub expected: Temp,
+    pub observed: Temp,
+    /// How far the observed scalar is from the expected one, in the target unit
+    pub drift: Decimal,
+    pub passed: bool,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {}: {} -> {} (expected {}, drift {})",
+            if self.passed { "PASS" } else { "FAIL" },
+            self.name,
+            self.original,
+            self.observed,
+            self.expected,
+            self.drift.normalize()
+        )
+    }
+}
+
+/// Converts every reference value from every unit into every unit, passing each conversion that
+/// lands within `tolerance` kelvin of the expected value.
+pub fn run(tolerance: Decimal) -> Vec<Check> {
+    let units =
+        ['C', 'F', 'K'].map(|unit| TempUnit::try_from(unit).expect("C, F, and K are units"));
+    let mut checks = Vec::new();
+
+    for (name, scalars) in REFERENCES {
+        let temps: Vec<Temp> = scalars
+            .into_iter()
+            .zip(units)
+            .map(|(scalar, unit)| Temp { scalar, unit })
+            .collect();
+
+        for original in &temps {
+            for expected in &temps {
+                let observed = original.convert_to(expected.unit);
+                checks.push(Check {
+                    name,
+                    original: *original,
+                    expected: *expected,
+                    observed,
+                    drift: (observed.scalar - expected.scalar).abs(),
+                    passed: observed.approx_eq(*expected, tolerance),
+                });
+            }
+        }
+    }
+    checks
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of converting a temperature into another unit and back
+pub struct RoundTrip {
+    pub via: TempUnit,
+    pub recovered: Temp,
+    /// How far the recovered scalar is from the original, in the original's unit
+    pub drift: Decimal,
+    pub passed: bool,
+}
+
+/// Converts `temp` into every other unit and back, passing each round trip that recovers it
+/// within `tolerance` kelvin.
+pub fn round_trips(temp: Temp, tolerance: Decimal) -> Vec<RoundTrip> {
+    TempUnit::all()
+        .filter(|via| *via != temp.unit)
+        .map(|via| {
+            let drift = temp.round_trip_drift(via);
+            RoundTrip {
+                via,
+                recovered: temp.convert_to(via).convert_to(temp.unit),
+                drift,
+                // Measured as a difference, since converting the recovered value to kelvin could
+                // round the drift away
+                passed: TempDelta::of(drift, temp.unit).kelvin.abs() <= tolerance,
+            }
+        })
+        .collect()
+}
+
+/// Lays out round trips as a table with a row for each unit they went through. Recovered values
+/// are shown in full, so that the drift can be seen in them.
+pub fn round_trip_table(trips: &[RoundTrip]) -> String {
+    let full = DisplayOptions {
+        max_decimals: None,
+        ..DisplayOptions::default()
+    };
+    let rows: Vec<[String; 4]> = trips
+        .iter()
+        .map(|trip| {
+            [
+                trip.via.full_name().to_string(),
+                trip.recovered.format_with(&full),
+                trip.drift.normalize().to_string(),
+                if trip.passed { "PASS" } else { "FAIL" }.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["Via", "Recovered", "Drift", ""].map(String::from);
+    let widths: Vec<usize> = (0..4)
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    [&header]
+        .into_iter()
+        .chain(&rows)
+        .map(|row| {
+            format!(
+                "{:<via$}  {:<recovered$}  {:<drift$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                via = widths[0],
+                recovered = widths[1],
+                drift = widths[2],
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{round_trip_table, round_trips, run, Check};
+    use crate::temp::Temp;
+    use crate::temp::DEFAULT_TOLERANCE;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_self_test_passes() {
+        let checks = run(DEFAULT_TOLERANCE);
+        assert_eq!(checks.len(), 36);
+        assert!(checks.iter().all(|check| check.passed));
+    }
+
+    #[test]
+    fn test_self_test_is_exact() {
+        let checks = run(dec!(0));
+        assert!(checks
+            .iter()
+            .all(|check| check.passed && check.drift == dec!(0)));
+        assert!(checks[0].to_string().starts_with("PASS  "));
+
+        let failed = Check {
+            passed: false,
+            ..checks[0].clone()
+        };
+        assert!(failed.to_string().starts_with("FAIL  "));
+    }
+
+    #[test]
+    fn test_round_trips() -> Result<(), crate::error::TempError> {
+        let boiling = "100C".parse::<Temp>()?;
+        let trips = round_trips(boiling, dec!(0));
+        assert_eq!(trips.len(), 2);
+        assert!(trips
+            .iter()
+            .all(|trip| trip.passed && trip.recovered == boiling && trip.drift == dec!(0)));
+        assert_eq!(
+            round_trip_table(&trips),
+            "Via         Recovered  Drift\n\
+             Fahrenheit  100 C      0      PASS\n\
+             Kelvin      100 C      0      PASS"
+        );
+
+        // 1 F is a repeating decimal in C and K, so going through them loses a little
+        let trips = round_trips("1F".parse()?, dec!(0));
+        assert!(trips
+            .iter()
+            .all(|trip| !trip.passed && !trip.drift.is_zero()));
+        assert!(round_trips("1F".parse()?, DEFAULT_TOLERANCE)
+            .iter()
+            .all(|trip| trip.passed));
+        Ok(())
+    }
+}
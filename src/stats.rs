@@ -0,0 +1,292 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, Write};
+
+use rust_decimal::Decimal;
+
+use crate::batch::clean_line;
+use crate::error::TempError;
+use crate::temp::{Temp, TempUnit};
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+/// A running count, minimum, maximum, and mean of temperatures in any units. Everything is kept in
+/// kelvin, so mixed units can be summarized together and read back in any unit.
+pub struct TempStats {
+    count: usize,
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    total: Decimal,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A [`TempStats`] read out in one unit
+pub struct Summary {
+    pub count: usize,
+    pub min: Temp,
+    pub max: Temp,
+    pub mean: Temp,
+}
+
+impl TempStats {
+    /// Adds one temperature to the summary. A temperature too large to convert to kelvin, or to
+    /// add to the total, is an error, and leaves the summary as it was.
+    pub fn push(&mut self, temp: Temp) -> Result<(), TempError> {
+        let kelvin = temp.checked_convert_to(TempUnit::KELVIN)?.scalar;
+        self.total = self
+            .total
+            .checked_add(kelvin)
+            .ok_or(TempError::TotalOverflow(temp))?;
+        self.count += 1;
+        self.min = Some(self.min.map_or(kelvin, |min| min.min(kelvin)));
+        self.max = Some(self.max.map_or(kelvin, |max| max.max(kelvin)));
+        Ok(())
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The summary in `unit`, or `None` if no temperatures were added. It's a
+    /// [`TempError::Overflow`] if the minimum, maximum or mean is too large to convert to `unit`.
+    pub fn in_unit(&self, unit: TempUnit) -> Result<Option<Summary>, TempError> {
+        let in_unit = |scalar| {
+            Temp {
+                scalar,
+                unit: TempUnit::KELVIN,
+            }
+            .checked_convert_to(unit)
+        };
+        let (Some(min), Some(max)) = (self.min, self.max) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Summary {
+            count: self.count,
+            min: in_unit(min)?,
+            max: in_unit(max)?,
+            mean: in_unit(self.total / Decimal::from(self.count))?,
+        }))
+    }
+}
+
+/// Summarizes one temperature per line in a single pass, without keeping the lines around. Each
+/// line is read with `read` (usually parsing and converting it), and lines it fails on, or whose
+/// temperature is too large to add to the summary, are skipped with a note written to `notes`.
+/// Blank lines are skipped silently.
+pub fn read_stats(
+    source: impl BufRead,
+    read: impl Fn(&str) -> Result<Temp, TempError>,
+    notes: impl Write,
+) -> io::Result<TempStats> {
+    let mut stats = TempStats::default();
+    read_temps(source, read, notes, |temp| stats.push(temp))?;
+    Ok(stats)
+}
+
+/// Like [`read_stats`], but summarizes the temperatures read in each unit on their own, to see
+/// what a file of mixed units is made of. The groups come in the order of [`TempUnit::all`], each
+/// with the unit its temperatures were read in, and units that nothing was read in are left out.
+pub fn read_stats_by_unit(
+    source: impl BufRead,
+    read: impl Fn(&str) -> Result<Temp, TempError>,
+    notes: impl Write,
+) -> io::Result<Vec<(TempUnit, TempStats)>> {
+    let mut groups: Vec<(TempUnit, TempStats)> = TempUnit::all()
+        .map(|unit| (unit, TempStats::default()))
+        .collect();
+    read_temps(source, read, notes, |temp| {
+        match groups.iter_mut().find(|(unit, _)| *unit == temp.unit) {
+            Some((_, stats)) => stats.push(temp),
+            None => Ok(()),
+        }
+    })?;
+    groups.retain(|(_, stats)| stats.count() > 0);
+    Ok(groups)
+}
+
+/// Passes each temperature that `read` gets from a line of `source` to `each`, noting the lines
+/// that either of them fails on in `notes`.
+fn read_temps(
+    source: impl BufRead,
+    read: impl Fn(&str) -> Result<Temp, TempError>,
+    mut notes: impl Write,
+    mut each: impl FnMut(Temp) -> Result<(), TempError>,
+) -> io::Result<()> {
+    for (index, line) in source.lines().enumerate() {
+        let line = line?;
+        let line = clean_line(index, &line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        match read(line).and_then(&mut each) {
+            Ok(()) => {}
+            Err(err) => writeln!(notes, "Skipping {line} on line {}: {err}", index + 1)?,
+        }
+    }
+    Ok(())
+}
+
+impl FromIterator<Temp> for TempStats {
+    fn from_iter<I: IntoIterator<Item = Temp>>(temps: I) -> Self {
+        let mut stats = TempStats::default();
+        stats.extend(temps);
+        stats
+    }
+}
+
+/// Temperatures too large to add to the summary are left out of it.
+impl Extend<Temp> for TempStats {
+    fn extend<I: IntoIterator<Item = Temp>>(&mut self, temps: I) {
+        temps.into_iter().for_each(|temp| {
+            let _ = self.push(temp);
+        });
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "count {}, min {}, max {}, mean {}",
+            self.count, self.min, self.max, self.mean
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_stats, read_stats_by_unit, TempStats};
+    use crate::error::TempError;
+    use crate::temp::{Temp, TempUnit, DEFAULT_TOLERANCE};
+
+    #[test]
+    fn test_collect_mixed_units() -> Result<(), TempError> {
+        let stats: TempStats = ["0C", "212F", "323.15K"]
+            .iter()
+            .map(|temp_str| temp_str.parse::<Temp>())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .collect();
+        assert_eq!(stats.count(), 3);
+
+        let celsius = stats
+            .in_unit(TempUnit::CELSIUS)?
+            .expect("three temperatures");
+        assert_eq!(celsius.min, "0C".parse::<Temp>()?);
+        assert!(celsius.max.approx_eq("100C".parse()?, DEFAULT_TOLERANCE));
+        assert!(celsius.mean.approx_eq("50C".parse()?, DEFAULT_TOLERANCE));
+        assert_eq!(celsius.mean.unit, TempUnit::CELSIUS);
+        assert_eq!(
+            stats
+                .in_unit(TempUnit::KELVIN)?
+                .map(|summary| summary.min.to_string()),
+            Some("273.15 K".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_stats() -> Result<(), Box<dyn std::error::Error>> {
+        let input = "50F\n\nwarm\n10C\n293.15K\n15d\n";
+        let mut notes = Vec::new();
+        let stats = read_stats(input.as_bytes(), |line| line.parse(), &mut notes)?;
+        let summary = stats
+            .in_unit(TempUnit::CELSIUS)?
+            .ok_or("three temperatures")?;
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.min.to_string(), "10 C");
+        assert_eq!(summary.max.to_string(), "20 C");
+        assert_eq!(summary.mean.to_string(), "13.3333333333 C");
+        assert_eq!(
+            String::from_utf8(notes)?,
+            "Skipping warm on line 3: warm is not a temperature, which is written as a number and a \
+             unit (ex. 100C)\n\
+             Skipping 15d on line 6: d is not a valid temperature unit\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let stats: TempStats = std::iter::empty().collect();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.in_unit(TempUnit::FAHRENHEIT), Ok(None));
+    }
+
+    #[test]
+    fn test_stats_overflow() -> Result<(), Box<dyn std::error::Error>> {
+        let huge = "50000000000000000000000000000K";
+        let input = format!("{huge}\n{huge}\n1K\n");
+        let mut notes = Vec::new();
+        let stats = read_stats(input.as_bytes(), |line| line.parse(), &mut notes)?;
+        assert_eq!(stats.count(), 2);
+        assert_eq!(
+            String::from_utf8(notes)?,
+            format!(
+                "Skipping {huge} on line 2: Adding 50000000000000000000000000000 K would make the \
+                 total too large to summarize\n"
+            )
+        );
+        assert_eq!(
+            stats.in_unit(TempUnit::FAHRENHEIT),
+            Err(TempError::Overflow(
+                Temp {
+                    scalar: "50000000000000000000000000000".parse()?,
+                    unit: TempUnit::KELVIN
+                },
+                TempUnit::FAHRENHEIT
+            ))
+        );
+
+        let mut stats = TempStats::default();
+        let hottest = Temp {
+            scalar: rust_decimal::Decimal::MAX,
+            unit: TempUnit::CELSIUS,
+        };
+        assert_eq!(
+            stats.push(hottest),
+            Err(TempError::Overflow(hottest, TempUnit::KELVIN))
+        );
+        assert_eq!(stats, TempStats::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_stats_by_unit() -> Result<(), Box<dyn std::error::Error>> {
+        let input = "300K\n50F\n10C\nwarm\n70F\n20C\n0C\n";
+        let mut notes = Vec::new();
+        let groups = read_stats_by_unit(input.as_bytes(), |line| line.parse(), &mut notes)?;
+        let summaries: Vec<(TempUnit, String)> = groups
+            .iter()
+            .map(|(unit, stats)| {
+                let summary = stats.in_unit(*unit)?.ok_or("a temperature in each group")?;
+                Ok::<_, Box<dyn std::error::Error>>((*unit, summary.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            summaries,
+            [
+                (
+                    TempUnit::CELSIUS,
+                    "count 3, min 0 C, max 20 C, mean 10 C".to_string()
+                ),
+                (
+                    TempUnit::FAHRENHEIT,
+                    "count 2, min 50 F, max 70 F, mean 60 F".to_string()
+                ),
+                (
+                    TempUnit::KELVIN,
+                    "count 1, min 300 K, max 300 K, mean 300 K".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            String::from_utf8(notes)?,
+            "Skipping warm on line 4: warm is not a temperature, which is written as a number and \
+             a unit (ex. 100C)\n"
+        );
+
+        let groups = read_stats_by_unit("1K\n".as_bytes(), |line| line.parse(), std::io::sink())?;
+        assert_eq!(groups.len(), 1);
+        Ok(())
+    }
+}
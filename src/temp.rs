@@ -1,26 +1,268 @@
+use std::cmp::{Ordering, Reverse};
 use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
 use std::str::FromStr;
 
-use rust_decimal::Decimal;
+use clap::ValueEnum;
+use num_format::{Locale, ToFormattedString};
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_decimal_macros::dec;
 
+use crate::error::TempError;
+
 const CEL: TempUnit = TempUnit('C');
 const FAH: TempUnit = TempUnit('F');
 const KEL: TempUnit = TempUnit('K');
+/// How many decimal places of a kelvin [`Temp::sort_key`] keeps.
+pub const SORT_KEY_SCALE: u32 = 10;
+/// How many decimal places of a kelvin [`Temp::canonical_string`] keeps.
+pub const CANONICAL_SCALE: u32 = 4;
+/// How far apart, in kelvin, two temperatures can be while still counting as equal. This absorbs
+/// the rounding from conversions that go through 5/9.
+pub const DEFAULT_TOLERANCE: Decimal = dec!(0.0001);
+/// How many decimal places are displayed by default, before long values get rounded.
+pub const DEFAULT_MAX_DECIMALS: u32 = 10;
+/// Where water freezes, in kelvin. Every unit's freezing point is worked out from this.
+const FREEZING_KELVIN: Decimal = dec!(273.15);
+/// The most characters a unit can take up at the end of a temperature (ex. the °C in 100°C).
+const MAX_UNIT_SUFFIX: usize = 3;
 const CONV_ERROR_MSG: &str =
     "Yikes! Seems you manually created this temperature, since we can't convert it";
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// One of the supported temperature units. Its field is private, so a unit can only come from the
+/// constants ([`TempUnit::CELSIUS`] and so on), [`TempUnit::parse`], or `TryFrom<char>`. Each of
+/// those only returns units found in the unit table, so every `TempUnit` can be converted.
+///
+/// Rather than matching on the constants, which would need changing whenever a unit is added, ask
+/// the unit itself through [`TempUnit::slope`], [`TempUnit::offset`], and [`TempUnit::symbol`].
 pub struct TempUnit(char);
 
 impl TryFrom<char> for TempUnit {
-    type Error = String;
+    type Error = TempError;
 
     fn try_from(unit: char) -> Result<Self, Self::Error> {
-        [CEL.0, FAH.0, KEL.0]
-            .contains(&unit.to_ascii_uppercase())
-            .then_some(Self(unit))
-            .ok_or(format!("{unit} is not a valid temperature unit"))
+        SCALES
+            .iter()
+            .map(|scale| scale.unit)
+            .find(|known| known.0 == unit.to_ascii_uppercase())
+            .ok_or(TempError::InvalidUnit(unit))
+    }
+}
+
+impl FromStr for TempUnit {
+    type Err = TempError;
+
+    /// Accepts the same spellings as [`TempUnit::parse`].
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::parse(name)
+    }
+}
+
+impl TempUnit {
+    pub const CELSIUS: Self = CEL;
+    pub const FAHRENHEIT: Self = FAH;
+    pub const KELVIN: Self = KEL;
+    /// The SI base unit, which [`Temp::to_base_unit`] converts into
+    pub const BASE: Self = KEL;
+
+    /// Every supported unit, in the order they are defined (C, F, then K). Each one can be passed
+    /// straight to [`Temp::convert_to`], as in `for unit in TempUnit::all()`, to list a
+    /// temperature in every unit; [`Temp::convert_all`] does the same, collected into a `Vec`.
+    pub fn all() -> impl Iterator<Item = Self> {
+        SCALES.iter().map(|scale| scale.unit)
+    }
+
+    /// Reads a unit from its letter (ex. C), full name (ex. celsius), or symbol (ex. °C), ignoring
+    /// case and surrounding whitespace.
+    pub fn parse(name: &str) -> Result<Self, TempError> {
+        let name = name.trim();
+        let mut chars = name.chars();
+        if let (Some(unit), None) = (chars.next(), chars.next()) {
+            return Self::try_from(unit);
+        }
+
+        SCALES
+            .iter()
+            .find(|scale| {
+                scale.full_name.eq_ignore_ascii_case(name)
+                    || scale.si_symbol.eq_ignore_ascii_case(name)
+            })
+            .map(|scale| scale.unit)
+            .ok_or_else(|| TempError::InvalidUnitName(name.to_string()))
+    }
+
+    /// The units that `name` is a likely typo of, closest first. Each unit's letter, name, and
+    /// symbol are compared to it, ignoring case, and kept if they are only a few edits away (one
+    /// edit for every three characters, but at least one). A single wrong letter is one edit from
+    /// every unit's letter, so all of them are suggested for it.
+    pub fn suggest(name: &str) -> Vec<Self> {
+        let name = name.trim().to_lowercase();
+        let allowed = (name.chars().count() / 3).max(1);
+        let mut close: Vec<(usize, Self)> = SCALES
+            .iter()
+            .filter_map(|scale| {
+                let letter = scale.unit.0.to_string();
+                [letter.as_str(), scale.full_name, scale.si_symbol]
+                    .into_iter()
+                    .map(|spelling| edit_distance(&name, &spelling.to_lowercase()))
+                    .min()
+                    .filter(|edits| *edits <= allowed)
+                    .map(|edits| (edits, scale.unit))
+            })
+            .collect();
+        close.sort_by_key(|(edits, _)| *edits);
+        close.into_iter().map(|(_, unit)| unit).collect()
+    }
+
+    /// Matches the unit written at the end of a temperature: its letter in any case, or its
+    /// symbol. Unlike [`TempUnit::parse`], names and surrounding whitespace aren't accepted.
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        let mut chars = suffix.chars();
+        if let (Some(unit), None) = (chars.next(), chars.next()) {
+            return Self::try_from(unit).ok();
+        }
+
+        SCALES
+            .iter()
+            .find(|scale| scale.si_symbol.eq_ignore_ascii_case(suffix))
+            .map(|scale| scale.unit)
+    }
+
+    /// The unit's name (ex. Celsius)
+    pub fn full_name(self) -> &'static str {
+        LinearScale::of(self).full_name
+    }
+
+    /// How the unit is written after a number when spelled out (ex. 100 degrees Celsius). Kelvin
+    /// is an absolute scale, counted in kelvins rather than degrees, so it is just 273.15 kelvin.
+    /// This is what the alternate `Display` (`{:#}`) writes.
+    pub fn spelled_out(self) -> &'static str {
+        LinearScale::of(self).spelled_out
+    }
+
+    /// The unit's symbol as written in the SI style, where kelvin doesn't take a degree sign
+    pub fn si_symbol(self) -> &'static str {
+        LinearScale::of(self).si_symbol
+    }
+
+    /// The letter the unit is written with (ex. C)
+    pub fn symbol(self) -> char {
+        self.0
+    }
+
+    /// How many of the unit's degrees make up one kelvin (ex. 1.8 for Fahrenheit). Together with
+    /// [`TempUnit::offset`], `reading = kelvin * slope + offset`, the same as a
+    /// [`CustomScale`](crate::custom::CustomScale).
+    pub fn slope(self) -> Decimal {
+        let (numerator, denominator) = LinearScale::of(self).slope;
+        Decimal::from(denominator) / Decimal::from(numerator)
+    }
+
+    /// The unit's reading at absolute zero (ex. -273.15 for Celsius)
+    pub fn offset(self) -> Decimal {
+        LinearScale::of(self).absolute_zero
+    }
+
+    /// Whether the unit is an absolute scale, starting at absolute zero (ex. kelvin), rather than
+    /// a relative one pinned to some other point (ex. Celsius). Only on an absolute scale does a
+    /// ratio like "twice as hot" mean anything.
+    pub fn is_absolute(self) -> bool {
+        LinearScale::of(self).is_absolute()
+    }
+}
+
+/// How a unit relates to kelvin: `kelvin = (scalar - absolute_zero) * slope`. The slope is kept as
+/// a fraction so that the ratio between two units (like 9/5) can be taken exactly.
+struct LinearScale {
+    unit: TempUnit,
+    full_name: &'static str,
+    /// How a number of the unit is spelled out, following the full name
+    spelled_out: &'static str,
+    si_symbol: &'static str,
+    /// Kelvin per degree, as a numerator and denominator
+    slope: (i64, i64),
+    /// The unit's reading at absolute zero
+    absolute_zero: Decimal,
+    /// The unit's reading at [`FREEZING_KELVIN`]. This follows from the slope and absolute zero,
+    /// but is written out so that conversions don't have to work it out each time
+    freezing_point: Decimal,
+}
+
+/// Every supported unit. A new linear unit only needs an entry here.
+static SCALES: [LinearScale; 3] = [
+    LinearScale {
+        unit: CEL,
+        full_name: "Celsius",
+        spelled_out: "degrees Celsius",
+        si_symbol: "°C",
+        slope: (1, 1),
+        absolute_zero: dec!(-273.15),
+        freezing_point: dec!(0),
+    },
+    LinearScale {
+        unit: FAH,
+        full_name: "Fahrenheit",
+        spelled_out: "degrees Fahrenheit",
+        si_symbol: "°F",
+        slope: (5, 9),
+        absolute_zero: dec!(-459.67),
+        freezing_point: dec!(32),
+    },
+    LinearScale {
+        unit: KEL,
+        full_name: "Kelvin",
+        spelled_out: "kelvin",
+        si_symbol: "K",
+        slope: (1, 1),
+        absolute_zero: dec!(0),
+        freezing_point: FREEZING_KELVIN,
+    },
+];
+
+impl LinearScale {
+    fn of(unit: TempUnit) -> &'static Self {
+        SCALES
+            .iter()
+            .find(|scale| scale.unit == unit)
+            .unwrap_or_else(|| panic!("{}: {}", CONV_ERROR_MSG, unit))
+    }
+
+    /// How many `to` degrees make up one `from` degree, as a numerator and denominator.
+    fn degree_ratio(from: &Self, to: &Self) -> (i64, i64) {
+        (from.slope.0 * to.slope.1, from.slope.1 * to.slope.0)
+    }
+
+    /// Restates a number of `from` degrees as `to` degrees, ignoring both units' offsets.
+    fn restate_degrees(degrees: Decimal, from: TempUnit, to: TempUnit) -> Decimal {
+        match Self::degree_ratio(Self::of(from), Self::of(to)) {
+            (numerator, denominator) if numerator == denominator => degrees,
+            (numerator, denominator) => {
+                degrees * Decimal::from(numerator) / Decimal::from(denominator)
+            }
+        }
+    }
+
+    /// Like [`LinearScale::restate_degrees`], but `None` if the result doesn't fit in a `Decimal`.
+    fn checked_restate_degrees(degrees: Decimal, from: TempUnit, to: TempUnit) -> Option<Decimal> {
+        match Self::degree_ratio(Self::of(from), Self::of(to)) {
+            (numerator, denominator) if numerator == denominator => Some(degrees),
+            (numerator, denominator) => {
+                let (numerator, denominator) =
+                    (Decimal::from(numerator), Decimal::from(denominator));
+                // Dividing first loses a little precision, so it's only done when multiplying
+                // first would overflow
+                degrees
+                    .checked_mul(numerator)
+                    .and_then(|scaled| scaled.checked_div(denominator))
+                    .or_else(|| degrees.checked_div(denominator)?.checked_mul(numerator))
+            }
+        }
+    }
+
+    /// Whether the unit starts at absolute zero, like kelvin does.
+    fn is_absolute(&self) -> bool {
+        self.absolute_zero.is_zero()
     }
 }
 
@@ -31,12 +273,210 @@ impl From<TempUnit> for char {
 }
 
 impl Display for TempUnit {
+    /// Writes the unit's letter, or with `{:#}`, its [`TempUnit::spelled_out`] name.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate() {
+            true => f.write_str(self.spelled_out()),
+            false => Display::fmt(&self.0, f),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Whether [`Temp::display_clamp`] had to pin the scalar to one of its bounds
+pub enum DisplayClamp {
+    InRange,
+    BelowMin,
+    AboveMax,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// What state pure water is in at a temperature, at a pressure of one atmosphere
+pub enum WaterPhase {
+    Solid,
+    Liquid,
+    Gas,
+}
+
+impl Display for WaterPhase {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        Display::fmt(&self.0, f)
+        f.write_str(match self {
+            WaterPhase::Solid => "solid",
+            WaterPhase::Liquid => "liquid",
+            WaterPhase::Gas => "gas",
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+/// How [`Temp::interpolate`] moves from one temperature to the other as `t` goes from 0 to 1
+pub enum Easing {
+    /// At a steady rate, straight along the line between them
+    #[default]
+    Linear,
+    /// Slowly at first, then faster (`t²`)
+    EaseIn,
+    /// Quickly at first, then slower (`1 - (1 - t)²`)
+    EaseOut,
+    /// Slowly at both ends, and fastest through the middle
+    EaseInOut,
+}
+
+impl Easing {
+    /// How far along the way `t` is, once eased. 0 and 1 are left as they are.
+    pub fn apply(self, t: Decimal) -> Decimal {
+        let ease_in = |t: Decimal| t * t;
+        let ease_out = |t: Decimal| Decimal::ONE - ease_in(Decimal::ONE - t);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => ease_in(t),
+            Easing::EaseOut => ease_out(t),
+            Easing::EaseInOut => match t < dec!(0.5) {
+                true => ease_in(t * Decimal::TWO) / Decimal::TWO,
+                false => (Decimal::ONE + ease_out(t * Decimal::TWO - Decimal::ONE)) / Decimal::TWO,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+/// How [`Temp::format_with`] rounds a scalar to the places it shows
+pub enum RoundMode {
+    /// Halves go away from zero (ex. 0.125 to 0.13, and -0.125 to -0.13)
+    #[default]
+    HalfAwayFromZero,
+    /// Halves go to the even neighbour (ex. 0.125 to 0.12, and 0.135 to 0.14), so that rounding
+    /// many values doesn't push their total up or down
+    HalfEven,
+    /// Extra places are cut off (ex. 0.129 to 0.12)
+    TowardZero,
+}
+
+impl From<RoundMode> for RoundingStrategy {
+    fn from(mode: RoundMode) -> Self {
+        match mode {
+            RoundMode::HalfAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+            RoundMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundMode::TowardZero => RoundingStrategy::ToZero,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The smallest change a temperature sensor can report, in the unit it measures in. A sensor's
+/// readings all lie on a grid of multiples of this step (ex. 0.0625 C for a DS18B20), which
+/// [`Resolution::quantize`] rounds other temperatures onto.
+pub struct Resolution {
+    pub step: Decimal,
+    pub unit: TempUnit,
+}
+
+impl Resolution {
+    /// The Maxim DS18B20 at its default 12-bit resolution
+    pub const DS18B20: Self = Resolution {
+        step: dec!(0.0625),
+        unit: CEL,
+    };
+    /// The Texas Instruments TMP117
+    pub const TMP117: Self = Resolution {
+        step: dec!(0.0078125),
+        unit: CEL,
+    };
+    /// The Sensirion SHT3x series (ex. SHT31)
+    pub const SHT3X: Self = Resolution {
+        step: dec!(0.01),
+        unit: CEL,
+    };
+    /// Every preset, by the name `--sensor` takes
+    pub const PRESETS: [(&'static str, Self); 3] = [
+        ("ds18b20", Self::DS18B20),
+        ("tmp117", Self::TMP117),
+        ("sht3x", Self::SHT3X),
+    ];
+
+    /// The temperature the sensor would report for `temp`, in `temp`'s unit. The nearest step is
+    /// found in the sensor's own unit, so a Fahrenheit reading lands on a Fahrenheit value that
+    /// the sensor can actually give. This fails only if `temp` is too large to convert into the
+    /// sensor's unit and back.
+    pub fn quantize(self, temp: Temp) -> Result<Temp, TempError> {
+        temp.checked_convert_to(self.unit)?
+            .quantize_to_resolution(self.step)
+            .checked_convert_to(temp.unit)
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    /// Looks up a preset by name, ignoring case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name.trim()))
+            .map(|(_, resolution)| *resolution)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::PRESETS.iter().map(|(preset, _)| *preset).collect();
+                format!(
+                    "{name} is not a known sensor, expected one of {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+/// How [`Temp::format_with`] writes the unit
+pub enum UnitStyle {
+    /// The unit's letter (ex. C)
+    #[default]
+    Letter,
+    /// The SI symbol (ex. °C)
+    Symbol,
+    /// The spelled-out name (ex. degrees Celsius, or kelvin), from [`TempUnit::spelled_out`]
+    Name,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Everything that can be changed about how [`Temp::format_with`] writes a temperature. The
+/// defaults match `Display`.
+pub struct DisplayOptions {
+    /// How many decimal places to show, rounding as `rounding` says. By default the scalar is
+    /// shown without trailing zeros
+    pub precision: Option<u32>,
+    /// When no precision is given, the most decimal places to show before rounding. This keeps
+    /// long expansions from the 5/9 slope readable, without touching shorter values
+    pub max_decimals: Option<u32>,
+    /// Show the scalar at the scale it was computed with (ex. 212.0000 rather than 212) when no
+    /// precision is given
+    pub keep_trailing_zeros: bool,
+    /// Never round a temperature at or above absolute zero to one below it
+    pub absolute_zero_guard: bool,
+    /// Write the scalar in scientific notation (ex. 6.96e7), with the precision and maximum
+    /// decimals applying to the mantissa
+    pub scientific: bool,
+    pub unit_style: UnitStyle,
+    /// Group digits and write the decimal separator the way this locale does
+    pub locale: Option<Locale>,
+    /// How the precision and maximum decimals round
+    pub rounding: RoundMode,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        DisplayOptions {
+            precision: None,
+            max_decimals: Some(DEFAULT_MAX_DECIMALS),
+            keep_trailing_zeros: false,
+            absolute_zero_guard: false,
+            scientific: false,
+            unit_style: UnitStyle::default(),
+            locale: None,
+            rounding: RoundMode::default(),
+        }
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 /// A representation of a temperature, in C, F, or K
 pub struct Temp {
     pub scalar: Decimal,
@@ -45,176 +485,4323 @@ pub struct Temp {
 
 #[allow(unused)]
 impl Temp {
+    /// The coldest possible temperature.
+    pub const ABSOLUTE_ZERO: Self = Self::kelvin(dec!(0));
+    /// The coldest temperature reached in a lab, about 38 picokelvin (Bremen, 2021).
+    pub const LOWEST_LAB_TEMPERATURE: Self = Self::kelvin(dec!(0.000000000038));
+    /// The cosmic microwave background.
+    pub const COSMIC_MICROWAVE_BACKGROUND: Self = Self::kelvin(dec!(2.72548));
+    /// The core of the Sun.
+    pub const SUN_CORE: Self = Self::kelvin(dec!(15_700_000));
+    /// The quark-gluon plasma produced at the LHC, the hottest temperature reached by people.
+    pub const QUARK_GLUON_PLASMA: Self = Self::kelvin(dec!(5_500_000_000_000));
+
+    /// Every extreme constant, coldest first.
+    ///
+    /// The Planck temperature (about 1.417e32 K) is deliberately missing, since it is well past
+    /// `Decimal::MAX` (about 7.9e28). The hottest entry here stays many orders of magnitude below
+    /// that limit, so it can still be converted to Fahrenheit (which scales by 1.8) without
+    /// overflowing.
+    pub const EXTREMES: [(&'static str, Self); 5] = [
+        ("absolute zero", Self::ABSOLUTE_ZERO),
+        ("lowest lab temperature", Self::LOWEST_LAB_TEMPERATURE),
+        (
+            "cosmic microwave background",
+            Self::COSMIC_MICROWAVE_BACKGROUND,
+        ),
+        ("core of the Sun", Self::SUN_CORE),
+        ("quark-gluon plasma", Self::QUARK_GLUON_PLASMA),
+    ];
+
+    /// Water freezes, at standard pressure.
+    pub const WATER_FREEZES: Self = Self::celsius(dec!(0));
+    /// A comfortable indoor temperature.
+    pub const ROOM_TEMPERATURE: Self = Self::celsius(dec!(20));
+    /// Typical human body temperature.
+    pub const BODY_TEMPERATURE: Self = Self::celsius(dec!(37));
+    /// Water boils, at standard pressure.
+    pub const WATER_BOILS: Self = Self::celsius(dec!(100));
+
+    /// Everyday temperatures that [`Temp::nearest_reference`] picks from, coldest first.
+    pub const REFERENCES: [(&'static str, Self); 4] = [
+        ("freezing", Self::WATER_FREEZES),
+        ("room temperature", Self::ROOM_TEMPERATURE),
+        ("body temperature", Self::BODY_TEMPERATURE),
+        ("boiling", Self::WATER_BOILS),
+    ];
+
+    /// The labels [`Temp::describe`] gives for ambient temperatures, coldest first, each with the
+    /// temperature it starts at. A label runs up to where the next one starts.
+    pub const DESCRIPTIONS: [(&'static str, Self); 6] = [
+        ("freezing", Self::ABSOLUTE_ZERO),
+        ("cold", Self::celsius(dec!(0))),
+        ("mild", Self::celsius(dec!(10))),
+        ("warm", Self::celsius(dec!(20))),
+        ("hot", Self::celsius(dec!(30))),
+        ("scalding", Self::celsius(dec!(45))),
+    ];
+
+    /// The temperatures, from a harsh winter to a harsh summer, that [`Temp::guess_units`] finds
+    /// most plausible for a reading.
+    pub const EVERYDAY_RANGE: (Self, Self) = (Self::celsius(dec!(-50)), Self::celsius(dec!(50)));
+
+    const fn kelvin(scalar: Decimal) -> Self {
+        Temp { scalar, unit: KEL }
+    }
+
+    const fn celsius(scalar: Decimal) -> Self {
+        Temp { scalar, unit: CEL }
+    }
+
+    /// A temperature of `scalar` degrees Celsius.
+    pub const fn from_celsius(scalar: Decimal) -> Self {
+        Self::celsius(scalar)
+    }
+
+    /// A temperature of `scalar` degrees Fahrenheit.
+    pub const fn from_fahrenheit(scalar: Decimal) -> Self {
+        Temp { scalar, unit: FAH }
+    }
+
+    /// A temperature of `scalar` kelvin.
+    pub const fn from_kelvin(scalar: Decimal) -> Self {
+        Self::kelvin(scalar)
+    }
+
+    /// The same unit with `scalar` in place of the number (ex. stepping a setpoint from 20 C to
+    /// 25 C). Nothing is converted, so to change the unit use [`Temp::convert_to`] instead.
+    pub const fn with_scalar(self, scalar: Decimal) -> Self {
+        Temp {
+            scalar,
+            unit: self.unit,
+        }
+    }
+
     /// For testing purposes only.
     fn new(scalar: Decimal, unit: TempUnit) -> Self {
         Temp { scalar, unit }
     }
 
-    pub fn to_celsius(self) -> Self {
-        match self.unit {
-            CEL => self,
-            FAH => Self {
-                scalar: (self.scalar - dec!(32)) * (dec!(5) / dec!(9)),
-                unit: CEL,
-            },
-            KEL => Self {
-                scalar: self.scalar - dec!(273.15),
-                unit: CEL,
-            },
-            _ => panic!("{} to Celsius: {}", CONV_ERROR_MSG, self),
-        }
+    /// Builds a temperature from a scalar and unit that were read separately, such as two fields
+    /// of a record. Surrounding whitespace in the scalar is ignored.
+    pub fn from_field_strings(scalar: &str, unit: char) -> Result<Self, TempError> {
+        let scalar = scalar.trim();
+        let conv_scalar =
+            Decimal::from_str(scalar).map_err(|_| TempError::InvalidScalar(scalar.to_string()))?;
+
+        Ok(Self {
+            scalar: conv_scalar,
+            unit: TempUnit::try_from(unit)?,
+        })
     }
 
-    pub fn to_fahrenheit(self) -> Self {
-        match self.unit {
-            CEL => Self {
-                scalar: self.scalar * dec!(1.8) + dec!(32),
-                unit: FAH,
-            },
-            FAH => self,
-            KEL => Self {
-                scalar: self.scalar * dec!(1.8) - dec!(459.67),
-                unit: FAH,
-            },
-            _ => panic!("{} to Fahrenheit: {}", CONV_ERROR_MSG, self),
+    /// Parses like `from_str`, but reads a bare number, with no unit, as being in `assumed`.
+    pub fn parse_assuming(temp_str: &str, assumed: TempUnit) -> Result<Self, TempError> {
+        match Decimal::from_str(temp_str.trim()) {
+            Ok(scalar) => Ok(Self {
+                scalar,
+                unit: assumed,
+            }),
+            Err(_) => temp_str.parse(),
         }
     }
 
-    pub fn to_kelvin(self) -> Self {
-        match self.unit {
-            CEL => Self {
-                scalar: self.scalar + dec!(273.15),
-                unit: KEL,
-            },
-            FAH => Self {
-                scalar: (self.scalar + dec!(459.67)) * (dec!(5) / dec!(9)),
-                unit: KEL,
-            },
-            KEL => self,
-            _ => panic!("{} to Kelvin: {}", CONV_ERROR_MSG, self),
+    /// Parses like `from_str`, but if the unit isn't recognized, suggests the units it may be a
+    /// typo of (see [`TempUnit::suggest`]) with a [`TempError::MisspelledUnit`], as in 15d or
+    /// 15celcius. When nothing is close enough to suggest, the error from `from_str` is returned.
+    pub fn try_parse_with_hint(temp_str: &str) -> Result<Self, TempError> {
+        temp_str.parse().map_err(|err| match err {
+            TempError::InvalidUnit(_)
+            | TempError::InvalidUnitName(_)
+            | TempError::InvalidScalar(_) => {
+                let trimmed = temp_str.trim();
+                let unit_at = trimmed
+                    .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.')))
+                    .unwrap_or(trimmed.len());
+                let (number, unit) = trimmed.split_at(unit_at);
+                let unit = unit.trim();
+                let suggestions = TempUnit::suggest(unit);
+                match Decimal::from_str(number).is_ok() && !suggestions.is_empty() {
+                    true => TempError::MisspelledUnit(unit.to_string(), suggestions),
+                    false => err,
+                }
+            }
+            err => err,
+        })
+    }
+
+    /// Parses like `from_str`, but recovers from common typos. Strict parsing is tried first, and
+    /// only if that fails are these recoveries attempted, together:
+    ///
+    /// 1. Surrounding whitespace and quotes (`"`, `'`, or `` ` ``) are dropped, as in `"100C"`.
+    /// 2. The unit is taken to be the first letter, so whitespace between the number and the unit
+    ///    is ignored, as in `100 C`.
+    /// 3. Anything after that first letter is dropped, as long as it is only punctuation,
+    ///    whitespace, or more unit letters, as in `100C.` or `100CC`.
+    ///
+    /// If the input still can't be read, the error from strict parsing is returned.
+    pub fn parse_loose(temp_str: &str) -> Result<Self, TempError> {
+        let strict_err = match temp_str.parse::<Self>() {
+            Ok(temp) => return Ok(temp),
+            Err(err) => err,
+        };
+
+        let unquoted = temp_str
+            .trim()
+            .trim_matches(|c| matches!(c, '"' | '\'' | '`'))
+            .trim();
+        let Some(unit_at) = unquoted.find(char::is_alphabetic) else {
+            return Err(strict_err);
+        };
+        let (scalar, rest) = unquoted.split_at(unit_at);
+        let mut rest = rest.chars();
+        let unit = rest.next().ok_or(strict_err.clone())?;
+        let junk_only = rest.all(|c| {
+            c.is_ascii_punctuation() || c.is_whitespace() || TempUnit::try_from(c).is_ok()
+        });
+
+        match junk_only {
+            true => Self::from_field_strings(scalar, unit).map_err(|_| strict_err),
+            false => Err(strict_err),
         }
     }
-}
 
-impl Display for Temp {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.scalar.normalize(), self.unit)
+    /// Parses a list of temperatures split by `separator` (ex. "0C,100C,37C" split by ','), with
+    /// whitespace around each one ignored. This fails fast: the first entry that can't be read
+    /// stops the parse, and is returned with its position in the list, counting from 0. An empty
+    /// entry, such as the one after a trailing separator, counts as unreadable.
+    pub fn parse_many(list: &str, separator: char) -> Result<Vec<Self>, (usize, TempError)> {
+        list.split(separator)
+            .enumerate()
+            .map(|(index, temp_str)| temp_str.parse().map_err(|err| (index, err)))
+            .collect()
     }
-}
 
-impl FromStr for Temp {
-    type Err = String;
+    /// Parses like `from_str`, but also rejects temperatures below absolute zero.
+    pub fn checked_from_str(temp_str: &str) -> Result<Self, TempError> {
+        temp_str.parse::<Self>()?.check_physical()
+    }
 
-    /// Permitted inputs are of the form 32F, 0C, or 273.6K. Other strings will fail.
-    fn from_str(temp_str: &str) -> Result<Self, Self::Err> {
-        match temp_str.chars().collect::<Vec<char>>().split_last() {
-            Some((split_unit, split_scalar)) => {
-                let conv_scalar = Decimal::from_str(split_scalar.iter().collect::<String>().trim());
-                let conv_unit = TempUnit::try_from(*split_unit);
-
-                if let (Ok(scalar), Ok(unit)) = (&conv_scalar, &conv_unit) {
-                    Ok(Self {
-                        scalar: *scalar,
-                        unit: *unit,
-                    })
-                } else {
-                    Err(format!(
-                        "Unable to convert {} into temperature:\n{}\n{}",
-                        temp_str,
-                        conv_scalar.map_or_else(|err| err.to_string(), |_| String::default()),
-                        conv_unit.map_or_else(|err| err, |_| String::default())
-                    ))
-                }
+    /// Reads a temperature as it comes from a sensor or other outside source, in three steps:
+    ///
+    /// 1. A unit written in `raw` is always used, so `default_unit` never overrides it.
+    /// 2. A bare number is read as being in `default_unit`. Without one, it is an error, as it
+    ///    would be for `from_str`.
+    /// 3. When `strict`, a temperature below absolute zero is rejected, whichever way its unit
+    ///    was decided.
+    pub fn from_reading(
+        raw: &str,
+        default_unit: Option<TempUnit>,
+        strict: bool,
+    ) -> Result<Self, TempError> {
+        let temp = match default_unit {
+            Some(unit) => Self::parse_assuming(raw, unit)?,
+            None => raw.parse()?,
+        };
+        match strict {
+            true => temp.check_physical(),
+            false => Ok(temp),
+        }
+    }
+
+    /// Passes the temperature through unchanged, unless it is below absolute zero.
+    pub fn check_physical(self) -> Result<Self, TempError> {
+        // Only a temperature far above absolute zero can be too large to have in kelvin
+        let below = match self.checked_convert(KEL) {
+            Some(kelvin) => kelvin.scalar < Decimal::ZERO,
+            None => self.scalar.is_sign_negative(),
+        };
+        match below {
+            true => Err(TempError::BelowAbsoluteZero(self)),
+            false => Ok(self),
+        }
+    }
+
+    /// Passes the temperature through unchanged, unless it is outside the `min` to `max` range.
+    /// Both bounds are inclusive, and may be in any unit: a temperature within `tolerance` kelvin
+    /// of a bound still counts as being on it. The comparison is made in kelvin, so a temperature
+    /// or bound too large to have in kelvin is a [`TempError::Overflow`].
+    pub fn check_range(
+        self,
+        min: Option<Self>,
+        max: Option<Self>,
+        tolerance: Decimal,
+    ) -> Result<Self, TempError> {
+        if min.is_none() && max.is_none() {
+            return Ok(self);
+        }
+        let kelvin = |temp: Self| temp.checked_convert_to(KEL).map(|kelvin| kelvin.scalar);
+        // Whether `low` is further below `high` than the tolerance, however far that is
+        let beyond = |low: Decimal, high: Decimal| {
+            low < high && high.checked_sub(low).is_none_or(|gap| gap > tolerance)
+        };
+        let own = kelvin(self)?;
+        if let Some(min) = min {
+            if beyond(own, kelvin(min)?) {
+                return Err(TempError::BelowMinimum(self, min));
+            }
+        }
+        if let Some(max) = max {
+            if beyond(kelvin(max)?, own) {
+                return Err(TempError::AboveMaximum(self, max));
             }
-            None => Err(format!("Invalid temperature value: {temp_str}")),
         }
+        Ok(self)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Temp;
-    use super::{CEL, FAH, KEL};
-    use rust_decimal::Decimal;
-    use rust_decimal_macros::dec;
+    /// Where the temperature sits between `min` (0) and `max` (1), in any units. Temperatures
+    /// outside the range come out below 0 or above 1. The fraction is the same in every unit, so
+    /// it is worked out in `min`'s unit, which avoids rounding when the units already match.
+    /// Returns `None` when `min` and `max` are the same temperature.
+    pub fn fraction_of(self, min: Self, max: Self) -> Option<Decimal> {
+        let width = max.convert_to(min.unit).scalar - min.scalar;
+        match width.is_zero() {
+            true => None,
+            false => Some((self.convert_to(min.unit).scalar - min.scalar) / width),
+        }
+    }
 
-    #[test]
-    fn test_valid_temp() -> Result<(), String> {
-        "15C".parse::<Temp>()?;
-        "122314234K".parse::<Temp>()?;
-        "-347.4f".parse::<Temp>()?;
-        Ok(())
+    /// Like [`Temp::fraction_of`], but pinned between 0 and 1.
+    pub fn fraction_of_clamped(self, min: Self, max: Self) -> Option<Decimal> {
+        self.fraction_of(min, max)
+            .map(|fraction| fraction.clamp(Decimal::ZERO, Decimal::ONE))
     }
 
-    #[test]
-    #[should_panic]
-    fn test_invalid_tempunit() {
-        "15d".parse::<Temp>().unwrap();
+    pub fn to_celsius(self) -> Self {
+        self.convert_to(CEL)
     }
 
-    #[test]
-    #[should_panic]
-    fn test_missing_tempunit() {
-        "1234.614".parse::<Temp>().unwrap();
+    pub fn to_fahrenheit(self) -> Self {
+        self.convert_to(FAH)
     }
 
-    #[test]
-    #[should_panic]
-    fn test_invalid_scalar() {
-        "123sdafsd23445.4F".parse::<Temp>().unwrap();
+    pub fn to_kelvin(self) -> Self {
+        self.convert_to(KEL)
     }
 
-    #[test]
-    fn test_to_celsius() -> Result<(), String> {
-        assert_eq!(
-            Temp::new(dec!(32), FAH).to_celsius(),
-            Temp::new(dec!(0), CEL)
-        );
-        assert_eq!(
-            Temp::new(dec!(234.63), KEL).to_celsius(),
-            Temp::new(dec!(-38.52), CEL)
-        );
-        assert_eq!(
-            Temp::new(dec!(-2345.7), CEL).to_celsius(),
-            Temp::new(dec!(-2345.7), CEL)
-        );
-        Ok(())
+    /// The temperature in kelvin as an exact fraction in lowest terms, with a positive denominator
+    /// (ex. 0 C is 5463/20). No `Decimal` rounding is involved, so this is exact even where
+    /// [`Temp::to_kelvin`] isn't (ex. 100 F is 55967/180).
+    ///
+    /// Both parts are worked out in `i128`, which holds about 38 digits. A `Decimal` has at most
+    /// 29, and the built-in scales add at most three more (two decimal places of absolute zero
+    /// and a slope under 10), so every temperature fits today. `None` is returned if a scale ever
+    /// needs more than `i128` can hold, rather than overflowing.
+    pub fn as_ratio_to_kelvin(self) -> Option<(i128, i128)> {
+        let scale = LinearScale::of(self.unit);
+        let digits = self.scalar.scale().max(scale.absolute_zero.scale());
+        let at_scale = |value: Decimal| {
+            10i128
+                .checked_pow(digits - value.scale())?
+                .checked_mul(value.mantissa())
+        };
+        let (num, den) = (i128::from(scale.slope.0), i128::from(scale.slope.1));
+        let numerator = at_scale(self.scalar)?
+            .checked_sub(at_scale(scale.absolute_zero)?)?
+            .checked_mul(num)?;
+        let denominator = 10i128.checked_pow(digits)?.checked_mul(den)?;
+
+        let divisor = gcd(numerator, denominator);
+        Some((numerator / divisor, denominator / divisor))
     }
 
-    #[test]
-    fn test_to_fahrenheit() -> Result<(), String> {
-        assert_eq!(
-            Temp::new(dec!(-18), CEL).to_fahrenheit(),
-            Temp::new(dec!(-0.4), FAH)
-        );
-        assert_eq!(
-            Temp::new(dec!(38653675), KEL).to_fahrenheit(),
-            Temp::new(dec!(69576155.33), FAH)
-        );
-        assert_eq!(
-            Temp::new(dec!(12), FAH).to_fahrenheit(),
-            Temp::new(dec!(12), FAH)
-        );
-        Ok(())
+    /// The temperature in kelvin, the SI base unit. This is the same as [`Temp::to_kelvin`], for
+    /// code that stores every temperature in one canonical scale.
+    pub fn to_base_unit(self) -> Self {
+        self.to_kelvin()
     }
 
-    #[test]
-    fn test_to_lord_kelvin() -> Result<(), String> {
-        assert_eq!(
-            Temp::new(dec!(25), CEL).to_kelvin(),
-            Temp::new(dec!(298.15), KEL)
-        );
-        // We love floating point errors
-        assert!(
-            Decimal::abs(
-                &(Temp::new(dec!(-2002), FAH).to_kelvin().scalar
-                    - Temp::new(dec!(-856.85), KEL).scalar)
-            ) < dec!(0.0001)
+    /// Reads a temperature stored in the base unit (kelvin) back, in `display_unit`.
+    pub fn from_base_unit(kelvin: Decimal, display_unit: TempUnit) -> Self {
+        Self::kelvin(kelvin).convert_to(display_unit)
+    }
+
+    /// The temperature in whole millikelvin, as stored by many sensors. Anything finer than a
+    /// millikelvin is rounded off (half away from zero), so this only gives `None` for
+    /// temperatures beyond the range of an `i64` (about 9.2 × 10¹⁵ K).
+    pub fn to_millikelvin(self) -> Option<i64> {
+        self.to_scaled_kelvin(3)
+    }
+
+    /// Reads a temperature stored in whole millikelvin back, in `display_unit`.
+    pub fn from_millikelvin(millikelvin: i64, display_unit: TempUnit) -> Self {
+        Self::from_base_unit(Decimal::new(millikelvin, 3), display_unit)
+    }
+
+    /// Like [`Temp::from_millikelvin`], but when `strict`, a reading below absolute zero is
+    /// rejected.
+    pub fn checked_from_millikelvin(
+        millikelvin: i64,
+        display_unit: TempUnit,
+        strict: bool,
+    ) -> Result<Self, TempError> {
+        Self::checked_from_scaled_kelvin(millikelvin, 3, display_unit, strict)
+    }
+
+    /// The temperature in whole centikelvin, rounded like [`Temp::to_millikelvin`].
+    pub fn to_centikelvin(self) -> Option<i64> {
+        self.to_scaled_kelvin(2)
+    }
+
+    /// Reads a temperature stored in whole centikelvin back, in `display_unit`.
+    pub fn from_centikelvin(centikelvin: i64, display_unit: TempUnit) -> Self {
+        Self::from_base_unit(Decimal::new(centikelvin, 2), display_unit)
+    }
+
+    /// The temperature in kelvin as a fixed-point integer with `scale` implied decimal places
+    /// (ex. 3 for millikelvin), rounded like [`Temp::to_millikelvin`]. `None` is returned if it
+    /// doesn't fit in an `i64`.
+    pub fn to_scaled_kelvin(self, scale: u32) -> Option<i64> {
+        let factor = Decimal::try_from_i128_with_scale(10i128.checked_pow(scale)?, 0).ok()?;
+        let scaled = self.checked_convert(KEL)?.scalar.checked_mul(factor)?;
+        i64::try_from(scaled.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)).ok()
+    }
+
+    /// Reads a fixed-point kelvin integer with `scale` implied decimal places (ex. 29315 at a
+    /// scale of 2 is 293.15 K) back, in `display_unit`. This fails if `scale` is more than a
+    /// `Decimal` can hold, or if the conversion into `display_unit` would overflow. Past about
+    /// 20 places, converting out of kelvin rounds off the last digits.
+    pub fn from_scaled_kelvin(
+        value: i64,
+        scale: u32,
+        display_unit: TempUnit,
+    ) -> Result<Self, TempError> {
+        let kelvin = Self::kelvin(
+            Decimal::try_new(value, scale).map_err(|_| TempError::InvalidScale(scale))?,
         );
-        assert_eq!(
-            Temp::new(dec!(0.0001), KEL).to_kelvin(),
-            Temp::new(dec!(0.0001), KEL)
+        kelvin
+            .checked_convert(display_unit)
+            .ok_or(TempError::Overflow(kelvin, display_unit))
+    }
+
+    /// Like [`Temp::from_scaled_kelvin`], but when `strict`, a reading below absolute zero is
+    /// also rejected.
+    pub fn checked_from_scaled_kelvin(
+        value: i64,
+        scale: u32,
+        display_unit: TempUnit,
+        strict: bool,
+    ) -> Result<Self, TempError> {
+        let temp = Self::from_scaled_kelvin(value, scale, display_unit)?;
+        match strict {
+            true => temp.check_physical(),
+            false => Ok(temp),
+        }
+    }
+
+    pub fn to_celsius_exact(self) -> Option<Self> {
+        self.convert_exact(CEL)
+    }
+
+    pub fn to_fahrenheit_exact(self) -> Option<Self> {
+        self.convert_exact(FAH)
+    }
+
+    pub fn to_kelvin_exact(self) -> Option<Self> {
+        self.convert_exact(KEL)
+    }
+
+    /// Converts into `unit` only if the result can be written exactly as a `Decimal`, which is
+    /// the case for most whole and tenths readings (ex. 212F or 98.6F into Celsius). Otherwise,
+    /// like for 100F into Celsius, there is no exact answer and `None` is returned.
+    ///
+    /// An exact result always converts back to exactly the original.
+    pub fn convert_exact(self, unit: TempUnit) -> Option<Self> {
+        if self.unit == unit {
+            return Some(self);
+        }
+
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let scaled = self
+            .scalar
+            .checked_sub(from_pivot)?
+            .checked_mul(Decimal::from(numerator))?;
+        let scalar = exact_quotient(scaled, denominator)?.checked_add(to_pivot)?;
+        Some(Self { scalar, unit })
+    }
+
+    /// The units the temperature converts into without rounding, in the order they are defined,
+    /// and always including its own. Going by [`Temp::convert_exact`], each of these also
+    /// converts back to exactly the original. Celsius and kelvin convert exactly into any unit with
+    /// a finite ratio (ex. 100 C into 212 F). Fahrenheit readings only convert exactly when their
+    /// distance from the freezing point (or from absolute zero, into kelvin) divides by 9 into a
+    /// finite decimal (ex. 212 F, but not 100 F).
+    pub fn lossless_units(self) -> Vec<TempUnit> {
+        TempUnit::all()
+            .filter(|unit| self.convert_exact(*unit).is_some())
+            .collect()
+    }
+
+    /// Like [`Temp::convert_exact`], but fails with [`TempError::Inexact`] when the result would
+    /// have to be rounded.
+    pub fn try_convert_exact(self, unit: TempUnit) -> Result<Self, TempError> {
+        self.convert_exact(unit)
+            .ok_or(TempError::Inexact(self, unit))
+    }
+
+    /// Converts into any supported unit, using the [`LinearScale`] of both units.
+    ///
+    /// Rather than always going through kelvin, the conversion pivots on a point that both units
+    /// read exactly: absolute zero when either unit is absolute, and the freezing point of water
+    /// otherwise. This keeps results like 32 F to 0 C and -459.67 F to 0 K exact, where a round
+    /// trip through kelvin would pick up rounding from the 5/9 slope. Results that
+    /// [`Temp::convert_exact`] can give are always exact, and only the rest are rounded.
+    pub fn convert_to(self, unit: TempUnit) -> Self {
+        if let Some(exact) = self.convert_exact(unit) {
+            return exact;
+        }
+
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let degrees = self.scalar - from_pivot;
+        let degrees = match numerator == denominator {
+            true => degrees,
+            false => degrees * (Decimal::from(numerator) / Decimal::from(denominator)),
+        };
+
+        Self {
+            scalar: degrees + to_pivot,
+            unit,
+        }
+    }
+
+    /// Converts into every supported unit, in the order they are defined.
+    pub fn convert_all(self) -> Vec<Self> {
+        self.convert_ordered(&UnitOrder::default())
+    }
+
+    /// Converts into each of the units in `order`, in that order.
+    pub fn convert_ordered(self, order: &UnitOrder) -> Vec<Self> {
+        order.0.iter().map(|unit| self.convert_to(*unit)).collect()
+    }
+
+    /// Converts into `preferred` if it's one of the `active` units, and into `fallback` (whether
+    /// or not it's active) otherwise. This lets a display degrade gracefully when the unit it
+    /// would rather use has been turned off.
+    pub fn to_unit_or(self, preferred: TempUnit, fallback: TempUnit, active: &UnitOrder) -> Self {
+        match active.0.contains(&preferred) {
+            true => self.convert_to(preferred),
+            false => self.convert_to(fallback),
+        }
+    }
+
+    /// Converts like [`Temp::convert_to`], along with an estimate of how far the converted scalar
+    /// is from the exact result (positive when the result came out too high).
+    ///
+    /// The only lossy step in a conversion is scaling by the ratio of the two units' slopes (like
+    /// 5/9), which `Decimal` has to round. The error is found by checking that step with exact
+    /// integer multiplication instead: `(result - pivot) * denominator - degrees * numerator` is
+    /// zero for an exact result, and dividing it by the denominator gives the error. Conversions
+    /// that only shift by an offset or scale by a ratio with a finite decimal (like 9/5) report
+    /// zero. Since the check is itself done in `Decimal`, errors in results that already use all
+    /// 28 digits are only estimated to within a digit or so. Like
+    /// [`Temp::checked_convert_to`], a result (or a step of the check) too large for a `Decimal`
+    /// is a [`TempError::Overflow`].
+    pub fn convert_with_error(self, unit: TempUnit) -> Result<(Self, Decimal), TempError> {
+        let converted = self.checked_convert_to(unit)?;
+        if self.unit == unit {
+            return Ok((converted, Decimal::ZERO));
+        }
+
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let overflow = || TempError::Overflow(self, unit);
+        let degrees = self.scalar.checked_sub(from_pivot).ok_or_else(overflow)?;
+        let expected = degrees
+            .checked_mul(Decimal::from(numerator))
+            .ok_or_else(overflow)?;
+        let residual = converted
+            .scalar
+            .checked_sub(to_pivot)
+            .and_then(|shifted| shifted.checked_mul(Decimal::from(denominator)))
+            .and_then(|scaled| scaled.checked_sub(expected))
+            .ok_or_else(overflow)?;
+        Ok((
+            converted,
+            (residual / Decimal::from(denominator)).normalize(),
+        ))
+    }
+
+    /// Converts into `unit` and back again, returning how far the result drifted from `self`, in
+    /// `self`'s unit. A conversion that loses nothing gives zero.
+    pub fn round_trip_drift(self, unit: TempUnit) -> Decimal {
+        (self.convert_to(unit).convert_to(self.unit).scalar - self.scalar).normalize()
+    }
+
+    /// Like [`Temp::round_trip_drift`], but for a result as `options` write it: how far the
+    /// temperature, rounded to the places `options` show, lands from `original` once converted
+    /// back, in `original`'s unit. 100 F written as 37.7777777778 C drifts by 0.00000000004 F,
+    /// while 212 F written as 100 C doesn't drift at all. Rounding in scientific notation isn't
+    /// counted.
+    pub fn display_drift(self, original: Self, options: &DisplayOptions) -> Decimal {
+        let shown = match options.precision.or(options.max_decimals) {
+            Some(decimals) => self.rescale(decimals),
+            None => self,
+        };
+        (shown.convert_to(original.unit).scalar - original.scalar).normalize()
+    }
+
+    /// Converts like [`Temp::convert_to`], but rejects temperatures below absolute zero and
+    /// reports results too large for a `Decimal` instead of panicking.
+    ///
+    /// This is the conversion to reach for with untrusted input, such as values read from users or
+    /// files. `convert_to` is fine for values that are known to be sensible, like constants, and
+    /// also for the rare cases where an unphysical temperature should still be converted.
+    pub fn try_convert_to(self, unit: TempUnit) -> Result<Self, TempError> {
+        self.check_physical()?.checked_convert_to(unit)
+    }
+
+    /// Converts like [`Temp::convert_to`], but reports results too large for a `Decimal` instead
+    /// of panicking. Unlike [`Temp::try_convert_to`], temperatures below absolute zero are still
+    /// converted.
+    pub fn checked_convert_to(self, unit: TempUnit) -> Result<Self, TempError> {
+        self.checked_convert(unit)
+            .ok_or(TempError::Overflow(self, unit))
+    }
+
+    /// Passes the temperature through unchanged, unless it's too large to convert into every unit,
+    /// which is a [`TempError::Overflow`] naming the first unit it can't be converted into. A
+    /// temperature that passes can be converted, compared and described without panicking.
+    pub fn check_convertible(self) -> Result<Self, TempError> {
+        TempUnit::all().try_for_each(|unit| self.checked_convert_to(unit).map(drop))?;
+        Ok(self)
+    }
+
+    /// Whether converting into `unit` stays within the range of a `Decimal`, so that
+    /// [`Temp::convert_to`] won't panic (ex. to disable a unit that a huge value can't be shown
+    /// in). Unlike [`Temp::try_convert_to`], temperatures below absolute zero aren't rejected.
+    pub fn can_convert_to(self, unit: TempUnit) -> bool {
+        self.checked_convert(unit).is_some()
+    }
+
+    /// Converts like [`Temp::convert_to`], but never panics: a result too large for a `Decimal`
+    /// is pinned to `Decimal::MAX` (or `Decimal::MIN`, for one too far below zero) instead. The
+    /// pinned result is physically wrong, and only useful as a bound, so check for it (or use
+    /// [`Temp::try_convert_to`]) wherever the real value matters.
+    pub fn saturating_convert_to(self, unit: TempUnit) -> Self {
+        self.checked_convert(unit).unwrap_or(Self {
+            scalar: match self.scalar.is_sign_negative() {
+                true => Decimal::MIN,
+                false => Decimal::MAX,
+            },
+            unit,
+        })
+    }
+
+    /// Converts like [`Temp::convert_to`], or gives `None` if the result doesn't fit in a
+    /// `Decimal`.
+    fn checked_convert(self, unit: TempUnit) -> Option<Self> {
+        if let Some(exact) = self.convert_exact(unit) {
+            return Some(exact);
+        }
+
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let degrees = self.scalar.checked_sub(from_pivot);
+        let degrees = match numerator == denominator {
+            true => degrees,
+            false => degrees.and_then(|degrees| {
+                degrees.checked_mul(Decimal::from(numerator) / Decimal::from(denominator))
+            }),
+        };
+        let scalar = degrees.and_then(|degrees| degrees.checked_add(to_pivot))?;
+        Some(Self { scalar, unit })
+    }
+
+    /// The conversion into `unit` as a spreadsheet formula with the scalar filled in, such as
+    /// `=(100*1.8)+32` for 100 C in F. Ratios with a finite decimal are written as one (1.8), and
+    /// the rest as a fraction (5/9), so the formula is as exact as the spreadsheet allows.
+    pub fn to_equation(self, unit: TempUnit) -> String {
+        format!(
+            "={}",
+            self.equation(&self.scalar.normalize().to_string(), unit)
+        )
+    }
+
+    /// The conversion into `unit` written out step by step, for showing how it's done: the
+    /// formula with the unit standing in for the number (ex. `(F-32)*5/9`), each step with its
+    /// constant and the value it leaves, and the result.
+    ///
+    /// ```json
+    /// {
+    ///   "formula": "(F-32)*5/9",
+    ///   "steps": [
+    ///     {"operation": "subtract", "constant": "32", "value": "180", "description": "..."},
+    ///     {"operation": "multiply", "constant": "5/9", "value": "100", "description": "..."}
+    ///   ],
+    ///   "result": {"scalar": "100", "unit": "C"}
+    /// }
+    /// ```
+    ///
+    /// Steps that wouldn't change the value (ex. adding 0) are left out, as they are from the
+    /// formula. The result is the same as [`Temp::checked_convert_to`] gives, including the
+    /// [`TempError::Overflow`] when a step leaves a value too large for a `Decimal`.
+    pub fn explain_json(self, unit: TempUnit) -> Result<serde_json::Value, TempError> {
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let (from, to) = (LinearScale::of(self.unit), LinearScale::of(unit));
+        let pivot_name = match from.is_absolute() || to.is_absolute() {
+            true => "absolute zero",
+            false => "the freezing point of water",
+        };
+        let result = self.checked_convert_to(unit)?;
+        let overflow = TempError::Overflow(self, unit);
+        let shifted = self
+            .scalar
+            .checked_sub(from_pivot)
+            .ok_or(overflow.clone())?;
+        let mut steps = Vec::new();
+        if !from_pivot.is_zero() {
+            steps.push(explain_offset(
+                -from_pivot,
+                shifted,
+                format!(
+                    "{} reads {} at {pivot_name}",
+                    self.unit,
+                    from_pivot.normalize()
+                ),
+            ));
+        }
+        if numerator != denominator {
+            let ratio = ratio_string(numerator, denominator);
+            let scaled = result.scalar.checked_sub(to_pivot).ok_or(overflow)?;
+            steps.push(serde_json::json!({
+                "operation": "multiply",
+                "constant": ratio,
+                "value": scaled.normalize().to_string(),
+                "description": format!("Multiply by {ratio}, the size of a {} degree in {unit} degrees", self.unit),
+            }));
+        }
+        if !to_pivot.is_zero() {
+            steps.push(explain_offset(
+                to_pivot,
+                result.scalar,
+                format!("{unit} reads {} at {pivot_name}", to_pivot.normalize()),
+            ));
+        }
+        Ok(serde_json::json!({
+            "formula": self.equation(&self.unit.to_string(), unit),
+            "steps": steps,
+            "result": {"scalar": result.scalar.normalize().to_string(), "unit": unit.to_string()},
+        }))
+    }
+
+    /// The conversion into `unit` as a formula applied to `operand`, without a leading `=`.
+    fn equation(self, operand: &str, unit: TempUnit) -> String {
+        let (from_pivot, (numerator, denominator), to_pivot) = self.conversion_steps(unit);
+        let shifted = match from_pivot.normalize() {
+            pivot if pivot.is_zero() => operand.to_string(),
+            pivot if pivot.is_sign_negative() => format!("{operand}+{}", pivot.abs()),
+            pivot => format!("{operand}-{pivot}"),
+        };
+        let scaled = match (numerator == denominator, shifted == operand) {
+            (true, _) => shifted,
+            (false, unshifted) => {
+                let ratio = ratio_string(numerator, denominator);
+                match unshifted {
+                    true => format!("{shifted}*{ratio}"),
+                    false => format!("({shifted})*{ratio}"),
+                }
+            }
+        };
+        match to_pivot.normalize() {
+            pivot if pivot.is_zero() => scaled,
+            pivot if pivot.is_sign_negative() => format!("({scaled})-{}", pivot.abs()),
+            pivot => format!("({scaled})+{pivot}"),
+        }
+    }
+
+    /// Breaks a conversion into the source unit's reading at the pivot, the ratio the degrees
+    /// above it are scaled by, and the target unit's reading at the same pivot.
+    fn conversion_steps(self, unit: TempUnit) -> (Decimal, (i64, i64), Decimal) {
+        let (from, to) = (LinearScale::of(self.unit), LinearScale::of(unit));
+        let (from_pivot, to_pivot) = match from.is_absolute() || to.is_absolute() {
+            true => (from.absolute_zero, to.absolute_zero),
+            false => (from.freezing_point, to.freezing_point),
+        };
+        (from_pivot, LinearScale::degree_ratio(from, to), to_pivot)
+    }
+
+    /// Compares how hot two temperatures are, in any units, treating them as equal when they are
+    /// within `tolerance` kelvin of each other. Unlike `Ord`, this never tells apart the same
+    /// temperature written in different units.
+    pub fn cmp_physical(self, other: Self, tolerance: Decimal) -> Ordering {
+        match self.approx_eq(other, tolerance) {
+            true => Ordering::Equal,
+            false => self.to_kelvin().scalar.cmp(&other.to_kelvin().scalar),
+        }
+    }
+
+    /// Checks whether two temperatures, in any units, are within `tolerance` kelvin of each other.
+    /// A tolerance of zero requires them to be exactly equal.
+    pub fn approx_eq(self, other: Self, tolerance: Decimal) -> bool {
+        self.distance(other) <= tolerance
+    }
+
+    /// How far apart two temperatures, in any units, are in kelvin. This is never negative, so it
+    /// can be used as a key for finding the closest of several temperatures. A distance too large
+    /// for a `Decimal` comes out as `Decimal::MAX`.
+    pub fn distance(self, other: Self) -> Decimal {
+        self.to_kelvin()
+            .scalar
+            .checked_sub(other.to_kelvin().scalar)
+            .map_or(Decimal::MAX, |distance| distance.abs())
+    }
+
+    /// Checks whether the temperature is within `tolerance` kelvin of a reference, such as
+    /// [`Temp::BODY_TEMPERATURE`]. This is [`Temp::approx_eq`] under a name that reads better
+    /// against constants.
+    pub fn is_approximately(self, reference: Self, tolerance: Decimal) -> bool {
+        self.approx_eq(reference, tolerance)
+    }
+
+    /// The closest of the [`Temp::REFERENCES`] within `tolerance` kelvin, along with its name.
+    pub fn nearest_reference(self, tolerance: Decimal) -> Option<(&'static str, Self)> {
+        Self::REFERENCES
+            .into_iter()
+            .filter(|(_, reference)| self.is_approximately(*reference, tolerance))
+            .min_by_key(|(_, reference)| self.distance(*reference))
+    }
+
+    /// The closest of `candidates` (ex. the entries of a calibration table), in any units. Of
+    /// candidates that are equally close, the first one wins.
+    pub fn nearest<'a>(&self, candidates: &'a [Temp]) -> Option<&'a Temp> {
+        candidates
+            .iter()
+            .min_by_key(|candidate| self.distance(**candidate))
+    }
+
+    /// What state water is in at the temperature, at one atmosphere. Exactly at a boundary,
+    /// water could be in either phase, so the boundaries are settled as ice at its melting point
+    /// and steam at its boiling point: [`Temp::WATER_FREEZES`] (0 C) is solid, and
+    /// [`Temp::WATER_BOILS`] (100 C) is gas. The boundaries are compared exactly, in any unit.
+    pub fn water_phase(self) -> WaterPhase {
+        match (
+            self.cmp_physical(Self::WATER_FREEZES, Decimal::ZERO),
+            self.cmp_physical(Self::WATER_BOILS, Decimal::ZERO),
+        ) {
+            (Ordering::Less | Ordering::Equal, _) => WaterPhase::Solid,
+            (_, Ordering::Less) => WaterPhase::Liquid,
+            _ => WaterPhase::Gas,
+        }
+    }
+
+    /// A rough word for how the temperature feels, from [`Temp::DESCRIPTIONS`] (ex. 35 C is hot).
+    pub fn describe(self) -> &'static str {
+        self.describe_with(&Self::DESCRIPTIONS)
+    }
+
+    /// An emoji for how the temperature feels: 🥶 up to cold, 🌡️ for mild and warm, and 🥵 from
+    /// hot up, going by the [`Temp::DESCRIPTIONS`] thresholds.
+    pub fn emoji(self) -> &'static str {
+        match self.describe() {
+            "freezing" | "cold" => "🥶",
+            "mild" | "warm" => "🌡️",
+            _ => "🥵",
+        }
+    }
+
+    /// Like [`Temp::describe`], but with other labels, given coldest first with the temperature
+    /// each one starts at. Anything colder than the first is given the first label.
+    pub fn describe_with(self, labels: &[(&'static str, Self)]) -> &'static str {
+        let kelvin = self.to_kelvin().scalar;
+        labels
+            .iter()
+            .rev()
+            .find(|(_, start)| start.to_kelvin().scalar <= kelvin)
+            .or(labels.first())
+            .map_or("", |(label, _)| label)
+    }
+
+    /// Reads `scalar` in every unit and converts each reading to `to`, for a number whose unit
+    /// has been lost. The pairs of reading and result are given most plausible first, going by:
+    ///
+    /// 1. How far, in kelvin, the reading is outside the [`Temp::EVERYDAY_RANGE`], so that
+    ///    readings of the weather or of a person come first. Readings below absolute zero are
+    ///    always outside it, so they come last.
+    /// 2. Then, how far it is from the closest of the [`Temp::REFERENCES`], so that 98.6 reads as
+    ///    body temperature in Fahrenheit rather than a warm day in Celsius.
+    ///
+    /// Equally plausible readings keep the order of [`TempUnit::all`]. Readings that can't be
+    /// converted without overflowing are left out.
+    pub fn guess_units(scalar: Decimal, to: TempUnit) -> Vec<(Self, Self)> {
+        let (low, high) = Self::EVERYDAY_RANGE;
+        let mut guesses: Vec<((Decimal, Decimal), Self, Self)> = TempUnit::all()
+            .filter_map(|unit| {
+                let reading = Self::new(scalar, unit);
+                let kelvin = reading.checked_convert(KEL)?.scalar;
+                let outside = (low.to_kelvin().scalar - kelvin)
+                    .max(kelvin - high.to_kelvin().scalar)
+                    .max(Decimal::ZERO);
+                let from_reference = Self::REFERENCES
+                    .iter()
+                    .map(|(_, reference)| (kelvin - reference.to_kelvin().scalar).abs())
+                    .min()
+                    .unwrap_or_default();
+                Some((
+                    (outside, from_reference),
+                    reading,
+                    reading.checked_convert(to)?,
+                ))
+            })
+            .collect();
+        guesses.sort_by_key(|(implausibility, ..)| *implausibility);
+        guesses
+            .into_iter()
+            .map(|(_, reading, result)| (reading, result))
+            .collect()
+    }
+
+    /// `count` evenly spaced temperatures from `start` to `end`, including both, in `start`'s
+    /// unit. The endpoints may be in different units, since the spacing is worked out in kelvin.
+    /// A count of 1 gives just `start`, and a count of 0 gives nothing. Endpoints too far apart
+    /// to space out within a `Decimal` are a [`TempError::Overflow`].
+    pub fn linspace(start: Self, end: Self, count: usize) -> Result<Vec<Self>, TempError> {
+        let from = start.checked_convert_to(KEL)?.scalar;
+        let to = end.checked_convert_to(KEL)?.scalar;
+        let overflow = || TempError::Overflow(end, start.unit);
+        let span = to.checked_sub(from).ok_or_else(overflow)?;
+        let steps = Decimal::from(count.saturating_sub(1).max(1));
+        (0..count)
+            .map(|index| {
+                let kelvin = span
+                    .checked_mul(Decimal::from(index))
+                    .and_then(|scaled| from.checked_add(scaled / steps))
+                    .ok_or_else(overflow)?;
+                Self::kelvin(kelvin).checked_convert_to(start.unit)
+            })
+            .collect()
+    }
+
+    /// The temperature `t` of the way from `a` to `b` (0 gives `a` and 1 gives `b`), with `t`
+    /// eased first (see [`Easing`]), in `a`'s unit. Like [`Temp::linspace`], the two may be in
+    /// different units, since the interpolation is done in kelvin.
+    pub fn interpolate(a: Self, b: Self, t: Decimal, easing: Easing) -> Self {
+        let (from, to) = (a.to_kelvin().scalar, b.to_kelvin().scalar);
+        Self::kelvin(from + (to - from) * easing.apply(t)).convert_to(a.unit)
+    }
+
+    /// Temperatures from `start` towards `end` (in either direction), `step` apart, in `start`'s
+    /// unit. `end` is included if a step lands on it, within [`DEFAULT_TOLERANCE`]. The step must
+    /// be a positive difference.
+    pub fn range(
+        start: Self,
+        end: Self,
+        step: TempDelta,
+    ) -> Result<impl Iterator<Item = Self>, TempError> {
+        if step.kelvin <= Decimal::ZERO {
+            return Err(TempError::InvalidStep(step.in_unit(start.unit).normalize()));
+        }
+        let (step, last) = match end < start {
+            true => (-step, Ordering::Less),
+            false => (step, Ordering::Greater),
+        };
+        Ok(
+            std::iter::successors(Some(start), move |temp| temp.checked_add(step))
+                .take_while(move |temp| temp.cmp_physical(end, DEFAULT_TOLERANCE) != last),
+        )
+    }
+
+    /// How far apart two temperatures are, as a number of degrees of `unit` that is never
+    /// negative (ex. 0 C and 100 C are 180 F apart).
+    pub fn abs_diff(a: Self, b: Self, unit: TempUnit) -> Decimal {
+        (a - b).in_unit(unit).abs()
+    }
+
+    /// How much hotter `self` is than `other`, the same as `self - other`.
+    pub fn delta_to(self, other: Self) -> TempDelta {
+        self - other
+    }
+
+    /// The temperature in kelvin, rounded to [`SORT_KEY_SCALE`] decimal places. Sorting by this
+    /// key once is cheaper than converting on every comparison, and matches the `Ord` order,
+    /// except that temperatures closer together than the key's scale will tie.
+    pub fn sort_key(self) -> Decimal {
+        self.to_kelvin().scalar.round_dp(SORT_KEY_SCALE)
+    }
+
+    /// The temperature in kelvin, always written with [`CANONICAL_SCALE`] decimal places and no
+    /// space before the unit (ex. 273.1500K for both 0 C and 32 F), for keying maps or dropping
+    /// duplicates by how hot temperatures are. Like [`Temp::sort_key`], temperatures closer
+    /// together than the scale share a key, and rounding is half away from zero.
+    pub fn canonical_string(self) -> String {
+        let kelvin = self.to_kelvin().rescale(CANONICAL_SCALE).scalar;
+        // A negative scalar that rounds to zero is just zero, as it is for display
+        let kelvin = match kelvin.is_zero() {
+            true => kelvin.abs(),
+            false => kelvin,
+        };
+        format!("{kelvin}{KEL}")
+    }
+
+    /// A 16-byte key whose byte-by-byte order is the order of how hot temperatures are, for
+    /// ordered key-value stores that can only compare bytes, so that a range of keys is a range
+    /// of temperatures in any unit.
+    ///
+    /// The key is the [`Temp::sort_key`] (kelvin, rounded to [`SORT_KEY_SCALE`] places) as a
+    /// whole number of 10⁻¹⁰ K, in a two's complement `i128` with its sign bit flipped, written
+    /// big-endian. Flipping the sign bit puts the negative values below absolute zero before the
+    /// positive ones, so even unphysical temperatures sort correctly. Temperatures closer together
+    /// than the scale share a key, as they do for `sort_key`, and `None` is returned for those
+    /// beyond about 1.7 × 10²⁸ K, which don't fit.
+    pub fn to_ordered_kelvin_bits(self) -> Option<[u8; 16]> {
+        let kelvin = self.checked_convert(KEL)?.scalar.round_dp(SORT_KEY_SCALE);
+        let fixed = 10i128
+            .checked_pow(SORT_KEY_SCALE - kelvin.scale())?
+            .checked_mul(kelvin.mantissa())?;
+        Some(((fixed as u128) ^ (1 << 127)).to_be_bytes())
+    }
+
+    /// Rounds the scalar to the nearest multiple of `step` in the current unit, with ties rounding
+    /// away from zero. The step must be positive, and a scalar with more multiples of it than a
+    /// `Decimal` can count is an overflow.
+    pub fn snap_to(self, step: Decimal) -> Result<Self, TempError> {
+        if step <= Decimal::ZERO {
+            return Err(TempError::InvalidStep(step));
+        }
+
+        let scalar = self
+            .scalar
+            .checked_div(step)
+            .map(|steps| steps.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero))
+            .and_then(|steps| steps.checked_mul(step))
+            .ok_or(TempError::Overflow(self, self.unit))?;
+        Ok(Self {
+            scalar,
+            unit: self.unit,
+        })
+    }
+
+    /// Rounds the scalar to the nearest value a sensor with a resolution of `step` could report,
+    /// in the current unit. This is [`Temp::snap_to`] for a sensor's grid (see [`Resolution`] for
+    /// presets), so ties round away from zero. A step that isn't positive has no grid to round
+    /// to, and a scalar too large to have steps that fine is already as coarse as a `Decimal` can
+    /// be, so both leave the temperature as it is.
+    pub fn quantize_to_resolution(self, step: Decimal) -> Self {
+        self.snap_to(step).unwrap_or(self)
+    }
+
+    /// Applies `f` to the scalar, keeping the unit, such as to correct a sensor's known bias
+    /// (ex. `temp.map_scalar(|scalar| scalar + dec!(0.5))`).
+    pub fn map_scalar(self, f: impl FnOnce(Decimal) -> Decimal) -> Self {
+        Self {
+            scalar: f(self.scalar),
+            unit: self.unit,
+        }
+    }
+
+    /// Multiplies the scalar by `factor`, keeping the unit (ex. a raw 985 F in tenths of a degree
+    /// scaled by 0.1 is 98.5 F). Returns `None` if the product doesn't fit in a `Decimal`.
+    pub fn checked_scale(self, factor: Decimal) -> Option<Self> {
+        Some(Self {
+            scalar: self.scalar.checked_mul(factor)?,
+            unit: self.unit,
+        })
+    }
+
+    /// Negates the scalar, keeping the unit (ex. 25 C to -25 C), such as to mirror a reading. This
+    /// works on the number alone, not the physical temperature, so the result depends on the unit:
+    /// the same temperature in F (77 F) inverts to -77 F, which isn't -25 C.
+    pub fn invert_sign(self) -> Self {
+        self.map_scalar(Neg::neg)
+    }
+
+    /// Sets the number of decimal places the scalar is stored with, padding with zeros or rounding
+    /// (half away from zero) as needed. This is useful for fixed-width fields, which should read
+    /// the scalar directly: `Display` normalizes it, so the padding never shows up there.
+    pub fn rescale(self, scale: u32) -> Self {
+        self.rescale_with(scale, RoundMode::HalfAwayFromZero)
+    }
+
+    /// Like [`Temp::rescale`], but rounding the way `mode` says.
+    pub fn rescale_with(self, scale: u32, mode: RoundMode) -> Self {
+        self.map_scalar(|scalar| rescale_decimal(scalar, scale, mode))
+    }
+
+    /// Rounds the scalar to the nearest whole degree, with halves going to the even neighbour
+    /// (ex. 36.5 C to 36 C, and 37.5 C to 38 C).
+    pub fn round_to_integer(self) -> Self {
+        self.round_to_integer_with(RoundingStrategy::MidpointNearestEven)
+    }
+
+    /// Rounds the scalar to a whole degree the way `strategy` says to.
+    pub fn round_to_integer_with(self, strategy: RoundingStrategy) -> Self {
+        self.map_scalar(|scalar| scalar.round_dp_with_strategy(0, strategy))
+    }
+
+    /// Like [`Temp::rescale`], except that a temperature at or above absolute zero is rounded up
+    /// instead if rounding as usual would take it below (ex. -459.669 F to one place is -459.6 F,
+    /// not -459.7 F).
+    pub fn rescale_physical(self, scale: u32) -> Self {
+        self.rescale_physical_with(scale, RoundMode::HalfAwayFromZero)
+    }
+
+    /// Like [`Temp::rescale_physical`], but rounding the way `mode` says.
+    pub fn rescale_physical_with(self, scale: u32, mode: RoundMode) -> Self {
+        let rounded = self.rescale_with(scale, mode);
+        match self.check_physical().is_ok() && rounded.check_physical().is_err() {
+            true => Self {
+                scalar: self
+                    .scalar
+                    .round_dp_with_strategy(scale, RoundingStrategy::ToPositiveInfinity),
+                unit: self.unit,
+            }
+            .rescale(scale),
+            false => rounded,
+        }
+    }
+
+    /// Rounds the scalar (half away from zero) to `max_decimals` places, but only if it has more
+    /// than that. Unlike [`Temp::rescale`], shorter scalars are left exactly as they are.
+    pub fn clamp_display_precision(self, max_decimals: u32) -> Self {
+        match self.scalar.normalize().scale() > max_decimals {
+            true => self.rescale(max_decimals),
+            false => self,
+        }
+    }
+
+    /// Pins the scalar, in its current unit, to the range a display can show (ex. -9.9 to 99.9 on
+    /// a 7-segment readout). This is purely cosmetic and says nothing about physical limits.
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn display_clamp(self, min: Decimal, max: Decimal) -> (Self, DisplayClamp) {
+        let outcome = if self.scalar < min {
+            DisplayClamp::BelowMin
+        } else if self.scalar > max {
+            DisplayClamp::AboveMax
+        } else {
+            DisplayClamp::InRange
+        };
+
+        (
+            Self {
+                scalar: self.scalar.clamp(min, max),
+                unit: self.unit,
+            },
+            outcome,
+        )
+    }
+
+    /// Formats the temperature like `Display`, but with the decimal and grouping separators of
+    /// the given locale. Only the scalar is affected; the unit is printed as-is.
+    pub fn to_locale_string(self, locale: &Locale) -> String {
+        format!("{} {}", self.locale_scalar(locale), self.unit)
+    }
+
+    /// Formats just the scalar with the decimal and grouping separators of the given locale.
+    pub fn locale_scalar(self, locale: &Locale) -> String {
+        group_scalar(self.scalar.normalize(), locale)
+    }
+
+    /// Formats the temperature as `options` describe. `Display` uses the default options.
+    pub fn format_with(&self, options: &DisplayOptions) -> String {
+        format!(
+            "{} {}",
+            self.format_scalar(options),
+            self.format_unit(options)
+        )
+    }
+
+    /// The scalar, once converted into `unit`, split into a mantissa and a power of ten for
+    /// custom scientific displays, so that `mantissa * 10^exponent` is the converted scalar. The
+    /// mantissa has a single nonzero digit before the point (ex. 6.957615533 and 7 for 6.96e7 F),
+    /// and keeps every digit of the scalar. Zero is split into 0 and 0.
+    pub fn to_scientific_parts(self, unit: TempUnit) -> (Decimal, i32) {
+        scientific_parts(self.convert_to(unit).scalar)
+    }
+
+    /// Formats just the scalar as `options` describe.
+    pub fn format_scalar(&self, options: &DisplayOptions) -> String {
+        if options.scientific {
+            return format_scientific(self.scalar, options);
+        }
+        let rescale = |scale| match options.absolute_zero_guard {
+            true => self.rescale_physical_with(scale, options.rounding),
+            false => self.rescale_with(scale, options.rounding),
+        };
+        let capped = match options.max_decimals {
+            Some(max_decimals) if self.scalar.normalize().scale() > max_decimals => {
+                rescale(max_decimals)
+            }
+            _ => *self,
+        };
+        let scalar = match (options.precision, options.keep_trailing_zeros) {
+            (Some(precision), _) => rescale(precision).scalar,
+            (None, true) => capped.scalar,
+            (None, false) => capped.scalar.normalize(),
+        };
+        // A negative scalar that rounds to zero is just zero (ex. -0.001 to two places is 0.00)
+        let scalar = match scalar.is_zero() {
+            true => scalar.abs(),
+            false => scalar,
+        };
+        match &options.locale {
+            Some(locale) => group_scalar(scalar, locale),
+            None => scalar.to_string(),
+        }
+    }
+
+    /// Formats just the unit as `options` describe.
+    pub fn format_unit(&self, options: &DisplayOptions) -> String {
+        match options.unit_style {
+            UnitStyle::Letter => self.unit.to_string(),
+            UnitStyle::Symbol => self.unit.si_symbol().to_string(),
+            UnitStyle::Name => self.unit.spelled_out().to_string(),
+        }
+    }
+}
+
+/// Writes a scalar, at its current scale, with the separators of a locale.
+fn group_scalar(scalar: Decimal, locale: &Locale) -> String {
+    let plain = scalar.abs().to_string();
+    let (int_digits, frac_digits) = plain.split_once('.').unwrap_or((&plain, ""));
+    let sign = if scalar.is_sign_negative() && !scalar.is_zero() {
+        locale.minus_sign()
+    } else {
+        ""
+    };
+    let grouped = int_digits
+        .parse::<u128>()
+        .expect("the integer part of a Decimal always fits in a u128")
+        .to_formatted_string(locale);
+
+    match frac_digits {
+        "" => format!("{sign}{grouped}"),
+        _ => format!("{sign}{grouped}{}{frac_digits}", locale.decimal()),
+    }
+}
+
+/// Writes a scalar as a mantissa from 1 to 10 (exclusive) and a power of ten, such as 6.96e7.
+/// Zero is written as 0e0.
+fn format_scientific(scalar: Decimal, options: &DisplayOptions) -> String {
+    let (mut mantissa, mut exponent) = scientific_parts(scalar);
+    let digits = mantissa.scale() + 1;
+
+    let round = |mantissa: Decimal| match options.precision {
+        Some(precision) => rescale_decimal(mantissa, precision, options.rounding),
+        None => match options.max_decimals.filter(|max| digits - 1 > *max) {
+            Some(max_decimals) => rescale_decimal(mantissa, max_decimals, options.rounding),
+            None => mantissa,
+        }
+        .normalize(),
+    };
+    // Rounding can carry into another digit (ex. 9.996 to 10.00), which moves up the exponent
+    let mut rounded = round(mantissa);
+    if rounded.abs() >= Decimal::TEN {
+        mantissa /= Decimal::TEN;
+        exponent += 1;
+        rounded = round(mantissa);
+    }
+    if rounded.is_zero() {
+        rounded = rounded.abs();
+    }
+
+    let mantissa = match &options.locale {
+        Some(locale) => group_scalar(rounded, locale),
+        None => rounded.to_string(),
+    };
+    format!("{mantissa}e{exponent}")
+}
+
+/// Sets the number of decimal places a scalar is stored with, padding with zeros or rounding the
+/// way `mode` says.
+fn rescale_decimal(scalar: Decimal, scale: u32, mode: RoundMode) -> Decimal {
+    let mut rounded = scalar.round_dp_with_strategy(scale, mode.into());
+    rounded.rescale(scale);
+    rounded
+}
+
+/// Splits a scalar into a mantissa from 1 to 10 (exclusive), keeping all of its digits, and a
+/// power of ten. Both parts of zero are zero.
+fn scientific_parts(scalar: Decimal) -> (Decimal, i32) {
+    let scalar = scalar.normalize();
+    let digits = scalar.mantissa().unsigned_abs().to_string().len() as u32;
+    let exponent = digits as i32 - 1 - scalar.scale() as i32;
+    (
+        Decimal::from_i128_with_scale(scalar.mantissa(), digits - 1),
+        exponent,
+    )
+}
+
+/// Looks up a locale by its tag (ex. de-DE, fr-FR, en-US), falling back to the language alone
+/// when the region isn't known on its own.
+pub fn parse_locale(tag: &str) -> Result<Locale, String> {
+    Locale::from_name(tag)
+        .or_else(|_| Locale::from_name(tag.split(['-', '_']).next().unwrap_or(tag)))
+        .map_err(|_| format!("{tag} is not a supported locale"))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Which units to show a temperature in, and in what order, such as the rows of a card. By
+/// default it's every unit in the order they are defined: C, F, then K.
+pub struct UnitOrder(Vec<TempUnit>);
+
+impl UnitOrder {
+    /// The units, in the order they should be shown.
+    pub fn units(&self) -> &[TempUnit] {
+        &self.0
+    }
+}
+
+impl Default for UnitOrder {
+    fn default() -> Self {
+        UnitOrder(TempUnit::all().collect())
+    }
+}
+
+impl FromStr for UnitOrder {
+    type Err = String;
+
+    /// Reads a comma-separated list of units (ex. K,C), where each unit appears at most once.
+    fn from_str(list: &str) -> Result<Self, Self::Err> {
+        let mut units = Vec::new();
+        for name in list.split(',') {
+            if name.trim().is_empty() {
+                return Err(format!(
+                    "Expected a comma-separated list of units, not {list}"
+                ));
+            }
+            let unit = TempUnit::parse(name).map_err(|err| err.to_string())?;
+            if units.contains(&unit) {
+                return Err(format!("{unit} is listed more than once"));
+            }
+            units.push(unit);
+        }
+        Ok(UnitOrder(units))
+    }
+}
+
+impl PartialOrd for Temp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Temperatures are ordered by how hot they are, regardless of unit. Different units that come
+/// out at exactly the same temperature are then ordered by unit, to stay consistent with `Eq`.
+impl Ord for Temp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_kelvin()
+            .scalar
+            .cmp(&other.to_kelvin().scalar)
+            .then_with(|| self.unit.0.cmp(&other.unit.0))
+            .then_with(|| self.scalar.cmp(&other.scalar))
+    }
+}
+
+/// The greatest common divisor of two numbers, which is never negative.
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Divides `value` by `denominator` if the quotient ends within the 28 decimal places a `Decimal`
+/// can hold, rather than rounding it.
+fn exact_quotient(value: Decimal, denominator: i64) -> Option<Decimal> {
+    let denominator = i128::from(denominator);
+    let (mut mantissa, mut scale) = (value.mantissa(), value.scale());
+    while mantissa % denominator != 0 {
+        if scale >= 28 {
+            return None;
+        }
+        mantissa = mantissa.checked_mul(10)?;
+        scale += 1;
+    }
+    Decimal::try_from_i128_with_scale(mantissa / denominator, scale).ok()
+}
+
+/// A degree ratio as a decimal when it has a finite one (1.8), and as a fraction (5/9) otherwise.
+fn ratio_string(numerator: i64, denominator: i64) -> String {
+    match exact_quotient(Decimal::from(numerator), denominator) {
+        Some(ratio) => ratio.normalize().to_string(),
+        None => format!("{numerator}/{denominator}"),
+    }
+}
+
+/// A step of [`Temp::explain_json`] that adds `offset` (or subtracts it, when negative), leaving
+/// `value`. `reason` says where the offset comes from.
+fn explain_offset(offset: Decimal, value: Decimal, reason: String) -> serde_json::Value {
+    let (operation, verb) = match offset.is_sign_negative() {
+        true => ("subtract", "Subtract"),
+        false => ("add", "Add"),
+    };
+    let offset = offset.abs().normalize();
+    serde_json::json!({
+        "operation": operation,
+        "constant": offset.to_string(),
+        "value": value.normalize().to_string(),
+        "description": format!("{verb} {offset}, since {reason}"),
+    })
+}
+
+/// Sorts temperatures from coldest to hottest, or hottest to coldest if `descending`, by their
+/// [`Temp::sort_key`]. The sort is stable, so the same temperature given more than once (even in
+/// different units, like 0C and 32F) keeps the order it was given in.
+pub fn sort_temps(temps: &mut [Temp], descending: bool) {
+    match descending {
+        true => temps.sort_by_key(|temp| Reverse(temp.sort_key())),
+        false => temps.sort_by_key(|temp| temp.sort_key()),
+    }
+}
+
+/// Compares against a temperature's string form (ex. "212F") by how hot it is, so the units don't
+/// have to match. The two count as equal within [`DEFAULT_TOLERANCE`] kelvin, and a string that
+/// doesn't parse is never equal.
+impl PartialEq<&str> for Temp {
+    fn eq(&self, other: &&str) -> bool {
+        other
+            .parse::<Temp>()
+            .is_ok_and(|other| self.approx_eq(other, DEFAULT_TOLERANCE))
+    }
+}
+
+/// The scalar in whatever unit the temperature is stored in, so 100 C and 212 F give different
+/// numbers. This is for generic code that only needs a `&Decimal`; check [`Temp::unit`] or convert
+/// first when the unit matters. `Borrow<Decimal>` isn't implemented for the same reason, since
+/// temperatures that compare equal can have different scalars.
+impl AsRef<Decimal> for Temp {
+    fn as_ref(&self) -> &Decimal {
+        &self.scalar
+    }
+}
+
+/// Writes the scalar without trailing zeros and rounded (half away from zero) to at most
+/// [`DEFAULT_MAX_DECIMALS`] places, followed by a space and the unit's letter (ex. -17.2222222222
+/// C for 1 F in Celsius). Zero is always written as 0, however small and negative it started out.
+/// Parsing the output gives back a temperature that displays exactly the same way, and is within
+/// half of the last displayed place of the original.
+impl Display for Temp {
+    /// Writes the temperature with the default [`DisplayOptions`], spelling out the unit with
+    /// `{:#}` (ex. 100 degrees Celsius).
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let options = DisplayOptions {
+            unit_style: match f.alternate() {
+                true => UnitStyle::Name,
+                false => UnitStyle::Letter,
+            },
+            ..DisplayOptions::default()
+        };
+        f.write_str(&self.format_with(&options))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A temperature paired with how many decimal places it should be shown with, so the intended
+/// precision travels with the value. Built with [`Temp::with_precision`].
+pub struct PreciseTemp {
+    pub temp: Temp,
+    pub decimals: u32,
+}
+
+impl Display for PreciseTemp {
+    /// Writes the temperature like `Display` for [`Temp`], but rounded (half away from zero) and
+    /// padded to its decimal places.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let options = DisplayOptions {
+            precision: Some(self.decimals),
+            ..DisplayOptions::default()
+        };
+        f.write_str(&self.temp.format_with(&options))
+    }
+}
+
+impl Temp {
+    /// Pairs the temperature with the number of decimal places it should be shown with.
+    pub fn with_precision(self, decimals: u32) -> PreciseTemp {
+        PreciseTemp {
+            temp: self,
+            decimals,
+        }
+    }
+
+    /// Writes the temperature rounded (half away from zero) to at most `decimals` places, as in
+    /// 37.78 C for 100 F in Celsius. Trailing zeros are dropped as usual for `Display`, where
+    /// [`Temp::with_precision`] keeps them.
+    pub fn to_string_rounded(self, decimals: u32) -> String {
+        self.rescale(decimals).to_string()
+    }
+}
+
+/// Converts a temperature into a unit through [`Temp::try_convert_to`], so generic code with
+/// `TryInto<Temp>` bounds can convert with `(temp, unit).try_into()?`.
+impl TryFrom<(Temp, TempUnit)> for Temp {
+    type Error = TempError;
+
+    fn try_from((temp, unit): (Temp, TempUnit)) -> Result<Self, Self::Error> {
+        temp.try_convert_to(unit)
+    }
+}
+
+/// The flag bits of a serialized `Decimal` that can be set: its scale, then its sign
+const DECIMAL_FLAGS_MASK: u32 = 0x80FF_0000;
+/// The most decimal places a `Decimal` can have
+const MAX_DECIMAL_SCALE: u32 = 28;
+
+impl Temp {
+    /// How many bytes [`Temp::to_le_bytes`] writes
+    pub const ENCODED_LEN: usize = 17;
+
+    /// The temperature as a fixed-size binary record, for storing many of them without parsing.
+    /// The layout is stable:
+    ///
+    /// - Bytes 0 to 15 are the scalar, as written by [`Decimal::serialize`]. That is a
+    ///   little-endian `u32` of flags (the scale in bits 16 to 23, and the sign in bit 31),
+    ///   followed by the 96-bit coefficient as three little-endian `u32`s, lowest first.
+    /// - Byte 16 is the unit's letter in ASCII (ex. `b'C'`).
+    ///
+    /// The scalar is kept in the temperature's own unit, so reading it back is exact.
+    pub fn to_le_bytes(self) -> [u8; Self::ENCODED_LEN] {
+        let mut bytes = [0; Self::ENCODED_LEN];
+        bytes[..16].copy_from_slice(&self.scalar.serialize());
+        bytes[16] = self.unit.symbol() as u8;
+        bytes
+    }
+
+    /// Reads a record written by [`Temp::to_le_bytes`]. Fails if the flags aren't ones a
+    /// `Decimal` can have, or if the last byte isn't the letter of a unit.
+    pub fn from_le_bytes(bytes: [u8; Self::ENCODED_LEN]) -> Result<Self, TempError> {
+        let mut scalar = [0; 16];
+        scalar.copy_from_slice(&bytes[..16]);
+        let flags = u32::from_le_bytes([scalar[0], scalar[1], scalar[2], scalar[3]]);
+        if flags & !DECIMAL_FLAGS_MASK != 0 || (flags >> 16) & 0xFF > MAX_DECIMAL_SCALE {
+            return Err(TempError::InvalidBytes(format!(
+                "{flags:#010x} are not the flags of a number"
+            )));
+        }
+        let unit = char::from(bytes[16]);
+        Ok(Self {
+            scalar: Decimal::deserialize(scalar),
+            unit: TempUnit::all()
+                .find(|known| known.symbol() == unit)
+                .ok_or(TempError::InvalidUnit(unit))?,
+        })
+    }
+}
+
+impl FromStr for Temp {
+    type Err = TempError;
+
+    /// Permitted inputs are of the form 32F, 0C, 273.6K, or 100°C, with any surrounding
+    /// whitespace ignored (including a `\r` left over from a CRLF line ending). The longest unit
+    /// at the end of the string wins. Other strings will fail, naming the whole unit if a number
+    /// comes before it (ex. Rø in 7.5Rø).
+    fn from_str(temp_str: &str) -> Result<Self, Self::Err> {
+        Self::parse_scalar_with(temp_str, |scalar| Decimal::from_str(scalar).ok())
+    }
+}
+
+impl Temp {
+    /// Parses like `from_str`, but reads the scalar as a whole number in `radix` (2 to 36). It can
+    /// start with a sign, then the usual prefix for its radix (`0x`, `0o`, or `0b`), as in raw
+    /// counts dumped by a sensor. The unit is still read from the end first, so 0x64C is 0x64 (100)
+    /// in Celsius rather than 0x64C.
+    ///
+    /// Panics if `radix` isn't between 2 and 36.
+    pub fn parse_radix(temp_str: &str, radix: u32) -> Result<Self, TempError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "{radix} is not a radix from 2 to 36"
+        );
+        Self::parse_scalar_with(temp_str, |scalar| {
+            let (negative, digits) = match scalar.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, scalar.strip_prefix('+').unwrap_or(scalar)),
+            };
+            let prefix = match radix {
+                16 => Some("0x"),
+                8 => Some("0o"),
+                2 => Some("0b"),
+                _ => None,
+            };
+            let digits = prefix
+                .and_then(|prefix| {
+                    digits
+                        .get(..prefix.len())
+                        .filter(|start| start.eq_ignore_ascii_case(prefix))
+                        .map(|_| &digits[prefix.len()..])
+                })
+                .unwrap_or(digits);
+            if digits.starts_with(['+', '-']) {
+                return None;
+            }
+            let magnitude = i128::from_str_radix(digits, radix).ok()?;
+            let scalar = Decimal::try_from_i128_with_scale(magnitude, 0).ok()?;
+            Some(match negative {
+                true => -scalar,
+                false => scalar,
+            })
+        })
+    }
+
+    /// Splits the unit off the end of a temperature, and reads the rest with `read_scalar`. When no
+    /// unit can be split off, what follows a leading number is an unknown unit (ex. celsius in
+    /// 20 celsius), and input that doesn't start with a number is a [`TempError::InvalidTemp`].
+    fn parse_scalar_with(
+        temp_str: &str,
+        read_scalar: impl Fn(&str) -> Option<Decimal>,
+    ) -> Result<Self, TempError> {
+        let temp_str = temp_str.trim();
+        if temp_str.is_empty() {
+            return Err(TempError::EmptyInput);
+        }
+        // Every way of splitting off the last 1 to 3 characters, shortest suffix first
+        let splits: Vec<(&str, &str)> = temp_str
+            .char_indices()
+            .rev()
+            .take(MAX_UNIT_SUFFIX)
+            .map(|(index, _)| temp_str.split_at(index))
+            .collect();
+
+        for (scalar, suffix) in splits.iter().rev() {
+            if let Some(unit) = TempUnit::from_suffix(suffix) {
+                let scalar = scalar.trim();
+                return read_scalar(scalar)
+                    .map(|scalar| Self { scalar, unit })
+                    .ok_or_else(|| TempError::InvalidScalar(scalar.to_string()));
+            }
+        }
+        for (scalar, suffix) in &splits {
+            if read_scalar(scalar.trim()).is_some() {
+                let mut chars = suffix.chars();
+                return Err(match (chars.next(), chars.next()) {
+                    (Some(unit), None) => TempError::InvalidUnit(unit),
+                    _ => TempError::InvalidUnitName(suffix.to_string()),
+                });
+            }
+        }
+        let unit_at = temp_str
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | '_')))
+            .unwrap_or(temp_str.len());
+        let (number, unit) = temp_str.split_at(unit_at);
+        Err(match read_scalar(number.trim()) {
+            Some(_) => TempError::InvalidUnitName(unit.trim().to_string()),
+            None => TempError::InvalidTemp(temp_str.to_string()),
+        })
+    }
+}
+
+/// How many single character insertions, deletions, or substitutions turn `from` into `to`.
+fn edit_distance(from: &str, to: &str) -> usize {
+    let to: Vec<char> = to.chars().collect();
+    let mut previous: Vec<usize> = (0..=to.len()).collect();
+    for (row, from_char) in from.chars().enumerate() {
+        let mut current = vec![row + 1];
+        for (column, to_char) in to.iter().enumerate() {
+            let substitution = previous[column] + usize::from(from_char != *to_char);
+            current.push(
+                substitution
+                    .min(previous[column + 1] + 1)
+                    .min(current[column] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[to.len()]
+}
+
+#[cfg(feature = "serde")]
+/// Temperatures are written in the same number-letter form they are read in (ex. "212F").
+impl serde::Serialize for Temp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{}{}", self.scalar.normalize(), self.unit))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Temp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Temp {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Temp".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let units: String = SCALES
+            .iter()
+            .flat_map(|scale| [scale.unit.0, scale.unit.0.to_ascii_lowercase()])
+            .collect();
+        schemars::json_schema!({
+            "type": "string",
+            "description": "A temperature, as a number followed by its unit (ex. 212F, -40C, 273.15K)",
+            "pattern": format!(r"^\s*[+-]?([0-9]+\.?[0-9]*|\.[0-9]+)[{units}]\s*$"),
+        })
+    }
+}
+
+/// The JSON Schema of a serialized [`Temp`].
+#[cfg(feature = "schema")]
+pub fn json_schema() -> serde_json::Value {
+    schemars::schema_for!(Temp).to_value()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+/// A signed difference between two temperatures. It is kept in kelvin, so deltas taken between
+/// different units can be compared directly. It is a different quantity from a [`Temp`], so the
+/// two can't be compared with each other, and only `Temp - Temp`, `Temp ± TempDelta`, and
+/// `TempDelta ± TempDelta` can be written (see `tests/ui` for the combinations that can't).
+pub struct TempDelta {
+    pub kelvin: Decimal,
+}
+
+/// Subtracting temperatures gives how far apart they are, rather than another temperature.
+///
+/// Panics if the difference doesn't fit in a `Decimal`; see [`Temp::checked_diff`].
+impl Sub for Temp {
+    type Output = TempDelta;
+
+    fn sub(self, other: Self) -> TempDelta {
+        self.checked_diff(other)
+            .expect("subtracting temperatures overflowed")
+    }
+}
+
+/// Warming a temperature by a difference keeps the temperature's unit.
+///
+/// Panics if the result doesn't fit in a `Decimal`; see [`Temp::checked_add`].
+impl Add<TempDelta> for Temp {
+    type Output = Temp;
+
+    fn add(self, delta: TempDelta) -> Temp {
+        self.checked_add(delta)
+            .expect("adding a temperature difference overflowed")
+    }
+}
+
+/// Cooling a temperature by a difference keeps the temperature's unit.
+///
+/// Panics if the result doesn't fit in a `Decimal`; see [`Temp::checked_sub`].
+impl Sub<TempDelta> for Temp {
+    type Output = Temp;
+
+    fn sub(self, delta: TempDelta) -> Temp {
+        self.checked_sub(delta)
+            .expect("subtracting a temperature difference overflowed")
+    }
+}
+
+impl Temp {
+    /// The temperature `delta` warmer, in the same unit, or `None` if it doesn't fit in a
+    /// `Decimal`.
+    pub fn checked_add(self, delta: TempDelta) -> Option<Self> {
+        let degrees = LinearScale::checked_restate_degrees(delta.kelvin, KEL, self.unit)?;
+        Some(Self {
+            scalar: self.scalar.checked_add(degrees)?,
+            unit: self.unit,
+        })
+    }
+
+    /// The temperature `delta` cooler, in the same unit, or `None` if it doesn't fit in a
+    /// `Decimal`.
+    pub fn checked_sub(self, delta: TempDelta) -> Option<Self> {
+        self.checked_add(-delta)
+    }
+
+    /// How far `self` is above `other`, like `self - other`. It's a [`TempError::Overflow`] if
+    /// either is too large to convert to kelvin, and a [`TempError::DeltaOverflow`] if the
+    /// difference is too large to keep in kelvin.
+    pub fn checked_diff(self, other: Self) -> Result<TempDelta, TempError> {
+        let (high, low) = (
+            self.checked_convert_to(KEL)?,
+            other.checked_convert_to(KEL)?,
+        );
+        let kelvin = high
+            .scalar
+            .checked_sub(low.scalar)
+            .ok_or(TempError::DeltaOverflow(KEL))?;
+        Ok(TempDelta { kelvin })
+    }
+}
+
+impl TempDelta {
+    /// A difference of `degrees` in `unit` (ex. 2 F), which only depends on the size of the
+    /// unit's degree.
+    ///
+    /// Panics if the difference doesn't fit in a `Decimal`; see [`TempDelta::checked_of`].
+    pub fn of(degrees: Decimal, unit: TempUnit) -> Self {
+        TempDelta {
+            kelvin: LinearScale::restate_degrees(degrees, unit, KEL),
+        }
+    }
+
+    /// Like [`TempDelta::of`], but a [`TempError::DeltaOverflow`] if the difference doesn't fit in
+    /// a `Decimal` once it's kept in kelvin.
+    pub fn checked_of(degrees: Decimal, unit: TempUnit) -> Result<Self, TempError> {
+        let kelvin = LinearScale::checked_restate_degrees(degrees, unit, KEL)
+            .ok_or(TempError::DeltaOverflow(KEL))?;
+        Ok(TempDelta { kelvin })
+    }
+
+    /// How many degrees of `unit` the difference spans.
+    ///
+    /// Panics if that doesn't fit in a `Decimal`; see [`TempDelta::checked_in_unit`].
+    pub fn in_unit(self, unit: TempUnit) -> Decimal {
+        LinearScale::restate_degrees(self.kelvin, KEL, unit)
+    }
+
+    /// Like [`TempDelta::in_unit`], but a [`TempError::DeltaOverflow`] if the number of degrees
+    /// doesn't fit in a `Decimal`.
+    pub fn checked_in_unit(self, unit: TempUnit) -> Result<Decimal, TempError> {
+        LinearScale::checked_restate_degrees(self.kelvin, KEL, unit)
+            .ok_or(TempError::DeltaOverflow(unit))
+    }
+
+    /// Writes the difference in degrees of `unit`, always with its sign (ex. +113.4 F).
+    ///
+    /// Panics if the number of degrees doesn't fit in a `Decimal`; see
+    /// [`TempDelta::checked_format_in`].
+    pub fn format_in(self, unit: TempUnit) -> String {
+        format!("{:+} {unit}", self.in_unit(unit).normalize())
+    }
+
+    /// Like [`TempDelta::format_in`], but a [`TempError::DeltaOverflow`] if the number of degrees
+    /// doesn't fit in a `Decimal`.
+    pub fn checked_format_in(self, unit: TempUnit) -> Result<String, TempError> {
+        Ok(format!(
+            "{:+} {unit}",
+            self.checked_in_unit(unit)?.normalize()
+        ))
+    }
+}
+
+/// The same difference in the opposite direction, so `-(a - b) == b - a`.
+impl Neg for TempDelta {
+    type Output = TempDelta;
+
+    fn neg(self) -> TempDelta {
+        TempDelta {
+            kelvin: -self.kelvin,
+        }
+    }
+}
+
+/// Differences add up, so warming by one and then another is warming by their sum.
+impl Add for TempDelta {
+    type Output = TempDelta;
+
+    fn add(self, other: Self) -> TempDelta {
+        TempDelta {
+            kelvin: self.kelvin + other.kelvin,
+        }
+    }
+}
+
+impl Sub for TempDelta {
+    type Output = TempDelta;
+
+    fn sub(self, other: Self) -> TempDelta {
+        self + -other
+    }
+}
+
+/// Scaling a difference (ex. twice the change) scales its size. Only differences can be scaled,
+/// since there's no meaning to twice a temperature.
+impl Mul<Decimal> for TempDelta {
+    type Output = TempDelta;
+
+    fn mul(self, factor: Decimal) -> TempDelta {
+        TempDelta {
+            kelvin: self.kelvin * factor,
+        }
+    }
+}
+
+impl Mul<TempDelta> for Decimal {
+    type Output = TempDelta;
+
+    fn mul(self, delta: TempDelta) -> TempDelta {
+        delta * self
+    }
+}
+
+/// The marks that set a temperature difference apart from a temperature, written between the
+/// number and the unit.
+const DELTA_MARKS: [char; 2] = ['Δ', 'd'];
+
+impl FromStr for TempDelta {
+    type Err = TempError;
+
+    /// Permitted inputs are a number, then Δ or d, then a unit as [`TempUnit::parse`] reads it (ex.
+    /// 5ΔC, 5dC, or -9 Δ°F). The mark is what tells a difference apart from a temperature, so
+    /// 5C is rejected here, just as 5dC is rejected as a [`Temp`].
+    fn from_str(delta_str: &str) -> Result<Self, Self::Err> {
+        let delta_str = delta_str.trim();
+        let Some((degrees, unit)) = delta_str.rsplit_once(DELTA_MARKS) else {
+            return Err(match delta_str.is_empty() {
+                true => TempError::EmptyInput,
+                false => TempError::InvalidDelta(delta_str.to_string()),
+            });
+        };
+        let degrees = degrees.trim();
+        let degrees = Decimal::from_str(degrees)
+            .map_err(|_| TempError::InvalidScalar(degrees.to_string()))?;
+        Ok(Self::of(degrees, TempUnit::parse(unit)?))
+    }
+}
+
+impl Display for TempDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kelvin.normalize(), KEL)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// How fast a temperature changes, like 2 C per minute. The time unit is only a label, so
+/// converting only restates the temperature part.
+pub struct TempRate {
+    pub delta: TempDelta,
+    pub per: String,
+}
+
+impl TempRate {
+    /// The rate's temperature change in degrees of `unit`, over the same time.
+    ///
+    /// Panics if that doesn't fit in a `Decimal`; see [`TempRate::checked_in_unit`].
+    pub fn in_unit(&self, unit: TempUnit) -> Decimal {
+        self.delta.in_unit(unit)
+    }
+
+    /// Like [`TempRate::in_unit`], but a [`TempError::DeltaOverflow`] if the change doesn't fit in
+    /// a `Decimal`.
+    pub fn checked_in_unit(&self, unit: TempUnit) -> Result<Decimal, TempError> {
+        self.delta.checked_in_unit(unit)
+    }
+
+    /// Writes the rate with its change in degrees of `unit` (ex. 3.6 F per minute).
+    ///
+    /// Panics if the change doesn't fit in a `Decimal`; see [`TempRate::checked_format_in`].
+    pub fn format_in(&self, unit: TempUnit) -> String {
+        format!("{} {unit} per {}", self.in_unit(unit).normalize(), self.per)
+    }
+
+    /// Like [`TempRate::format_in`], but a [`TempError::DeltaOverflow`] if the change doesn't fit
+    /// in a `Decimal`.
+    pub fn checked_format_in(&self, unit: TempUnit) -> Result<String, TempError> {
+        let change = self.checked_in_unit(unit)?.normalize();
+        Ok(format!("{change} {unit} per {}", self.per))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A quantity given per degree of some unit, like a resistor's 0.004 per C. Only the size of the
+/// degree matters here, never the unit's offset.
+pub struct TempCoefficient {
+    pub value: Decimal,
+    pub per: TempUnit,
+}
+
+impl TempCoefficient {
+    /// Restates the coefficient per degree of `unit`. A Fahrenheit degree is 5/9 the size of a
+    /// Celsius one, so the same coefficient is a smaller number per F than per C.
+    ///
+    /// Panics if the result doesn't fit in a `Decimal`; see [`TempCoefficient::checked_convert_to`].
+    pub fn convert_to(self, unit: TempUnit) -> Self {
+        Self {
+            value: LinearScale::restate_degrees(self.value, unit, self.per),
+            per: unit,
+        }
+    }
+
+    /// Like [`TempCoefficient::convert_to`], but a [`TempError::Overflow`] (naming the value in
+    /// the unit it's given per) if the result doesn't fit in a `Decimal`.
+    pub fn checked_convert_to(self, unit: TempUnit) -> Result<Self, TempError> {
+        let value = LinearScale::checked_restate_degrees(self.value, unit, self.per).ok_or(
+            TempError::Overflow(
+                Temp {
+                    scalar: self.value,
+                    unit: self.per,
+                },
+                unit,
+            ),
+        )?;
+        Ok(Self { value, per: unit })
+    }
+}
+
+impl Display for TempCoefficient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} per {}", self.value.normalize(), self.per)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_locale, DisplayClamp, DisplayOptions, Easing, Resolution, RoundMode, Temp,
+        TempCoefficient, TempDelta, TempRate, TempUnit, UnitOrder, UnitStyle, WaterPhase,
+    };
+    use super::{
+        sort_temps, LinearScale, CEL, DEFAULT_TOLERANCE, FAH, FREEZING_KELVIN, KEL, SCALES,
+    };
+    use crate::error::TempError;
+    use rust_decimal::{Decimal, RoundingStrategy};
+    use rust_decimal_macros::dec;
+    use std::cmp::Ordering;
+
+    /// Fails the test unless the temperatures are within `tolerance` kelvin of each other.
+    #[track_caller]
+    fn assert_approx_eq(actual: Temp, expected: Temp, tolerance: Decimal) {
+        assert!(
+            actual.approx_eq(expected, tolerance),
+            "{actual:?} is more than {tolerance} K from {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_valid_temp() -> Result<(), TempError> {
+        "15C".parse::<Temp>()?;
+        "122314234K".parse::<Temp>()?;
+        "-347.4f".parse::<Temp>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_trailing_carriage_return() -> Result<(), TempError> {
+        assert_eq!("100C\r".parse::<Temp>()?, Temp::new(dec!(100), CEL));
+        assert_eq!("-40 °F\r\n".parse::<Temp>()?, Temp::new(dec!(-40), FAH));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_suffixes() -> Result<(), TempError> {
+        assert_eq!("100°C".parse::<Temp>()?, Temp::new(dec!(100), CEL));
+        assert_eq!(" -40 °f ".parse::<Temp>()?, Temp::new(dec!(-40), FAH));
+        assert_eq!("273.15K".parse::<Temp>()?, Temp::new(dec!(273.15), KEL));
+        assert_eq!(
+            "7.5Rø".parse::<Temp>(),
+            Err(TempError::InvalidUnitName("Rø".to_string()))
+        );
+        assert_eq!("15d".parse::<Temp>(), Err(TempError::InvalidUnit('d')));
+        assert_eq!(
+            "1OOC".parse::<Temp>(),
+            Err(TempError::InvalidScalar("1OO".to_string()))
+        );
+        assert_eq!(
+            "°".parse::<Temp>(),
+            Err(TempError::InvalidTemp("°".to_string()))
+        );
+        assert_eq!(
+            "warm".parse::<Temp>(),
+            Err(TempError::InvalidTemp("warm".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lowercase_tempunit() -> Result<(), TempError> {
+        assert_eq!("-347.4f".parse::<Temp>()?, Temp::new(dec!(-347.4), FAH));
+        assert_eq!("32f".parse::<Temp>()?.to_celsius(), Temp::new(dec!(0), CEL));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_names() {
+        let names: Vec<(&str, &str)> = [CEL, FAH, KEL]
+            .into_iter()
+            .map(|unit| (unit.full_name(), unit.si_symbol()))
+            .collect();
+        assert_eq!(
+            names,
+            [("Celsius", "°C"), ("Fahrenheit", "°F"), ("Kelvin", "K")]
+        );
+    }
+
+    #[test]
+    fn test_parse_tempunit() {
+        for name in ["C", "c", "celsius", "Celsius", "°C", "°c", " CELSIUS "] {
+            assert_eq!(TempUnit::parse(name), Ok(CEL), "{name}");
+        }
+        for name in ["F", "fahrenheit", "°F"] {
+            assert_eq!(TempUnit::parse(name), Ok(FAH), "{name}");
+        }
+        for name in ["k", "Kelvin"] {
+            assert_eq!(TempUnit::parse(name), Ok(KEL), "{name}");
+        }
+        assert_eq!(
+            TempUnit::parse("celcius"),
+            Err(TempError::InvalidUnitName("celcius".to_string()))
+        );
+        assert_eq!(
+            TempUnit::parse("celcius").unwrap_err().to_string(),
+            "celcius is not a valid temperature unit"
+        );
+        assert_eq!(TempUnit::parse("d"), Err(TempError::InvalidUnit('d')));
+    }
+
+    #[test]
+    fn test_tempunit_from_str() {
+        assert_eq!("C".parse::<TempUnit>(), Ok(CEL));
+        assert_eq!("kelvin".parse::<TempUnit>(), Ok(KEL));
+        assert_eq!(
+            "XY".parse::<TempUnit>(),
+            Err(TempError::InvalidUnitName("XY".to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_tempunit() {
+        "15d".parse::<Temp>().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_missing_tempunit() {
+        "1234.614".parse::<Temp>().unwrap();
+    }
+
+    #[test]
+    fn test_empty_input() {
+        for blank in ["", "   ", "\t\n"] {
+            assert_eq!(blank.parse::<Temp>(), Err(TempError::EmptyInput));
+        }
+        assert_eq!(" 15C\n".parse::<Temp>(), Ok(Temp::new(dec!(15), CEL)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_scalar() {
+        "123sdafsd23445.4F".parse::<Temp>().unwrap();
+    }
+
+    #[test]
+    fn test_checked_from_str() -> Result<(), TempError> {
+        assert_eq!(
+            Temp::checked_from_str("-300C"),
+            Err(TempError::BelowAbsoluteZero(Temp::new(dec!(-300), CEL)))
+        );
+        assert_eq!("-300C".parse::<Temp>()?, Temp::new(dec!(-300), CEL));
+        assert_eq!(
+            Temp::checked_from_str("-459.67F")?,
+            Temp::new(dec!(-459.67), FAH)
+        );
+        assert_eq!(Temp::checked_from_str("0K")?, Temp::new(dec!(0), KEL));
+        assert!(Temp::checked_from_str("-0.01K").is_err());
+        let huge = Temp::new(Decimal::MAX, CEL);
+        assert_eq!(huge.check_physical(), Ok(huge));
+        assert_eq!(
+            huge.try_convert_to(KEL),
+            Err(TempError::Overflow(huge, KEL))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_field_strings() {
+        assert_eq!(
+            Temp::from_field_strings("100", 'C'),
+            Ok(Temp::new(dec!(100), CEL))
+        );
+        assert_eq!(
+            Temp::from_field_strings(" -40.5 ", 'F'),
+            Ok(Temp::new(dec!(-40.5), FAH))
+        );
+        assert_eq!(
+            Temp::from_field_strings("1OO", 'C'),
+            Err(TempError::InvalidScalar("1OO".to_string()))
+        );
+        assert_eq!(
+            Temp::from_field_strings("100", 'X'),
+            Err(TempError::InvalidUnit('X'))
+        );
+    }
+
+    #[test]
+    fn test_to_celsius() -> Result<(), String> {
+        assert_eq!(
+            Temp::new(dec!(32), FAH).to_celsius(),
+            Temp::new(dec!(0), CEL)
+        );
+        assert_eq!(
+            Temp::new(dec!(234.63), KEL).to_celsius(),
+            Temp::new(dec!(-38.52), CEL)
+        );
+        assert_eq!(
+            Temp::new(dec!(-2345.7), CEL).to_celsius(),
+            Temp::new(dec!(-2345.7), CEL)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_fahrenheit() -> Result<(), String> {
+        assert_eq!(
+            Temp::new(dec!(-18), CEL).to_fahrenheit(),
+            Temp::new(dec!(-0.4), FAH)
+        );
+        assert_eq!(
+            Temp::new(dec!(38653675), KEL).to_fahrenheit(),
+            Temp::new(dec!(69576155.33), FAH)
+        );
+        assert_eq!(
+            Temp::new(dec!(12), FAH).to_fahrenheit(),
+            Temp::new(dec!(12), FAH)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_lord_kelvin() -> Result<(), String> {
+        assert_eq!(
+            Temp::new(dec!(25), CEL).to_kelvin(),
+            Temp::new(dec!(298.15), KEL)
+        );
+        assert_eq!(
+            Temp::new(dec!(-2002), FAH).to_kelvin(),
+            Temp::new(dec!(-856.85), KEL)
+        );
+        assert_eq!(
+            Temp::new(dec!(0.0001), KEL).to_kelvin(),
+            Temp::new(dec!(0.0001), KEL)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ratio_to_kelvin() -> Result<(), TempError> {
+        let ratio =
+            |temp: &str| -> Result<_, TempError> { Ok(temp.parse::<Temp>()?.as_ratio_to_kelvin()) };
+        assert_eq!(ratio("0C")?, Some((5463, 20)));
+        assert_eq!(ratio("-40F")?, Some((4663, 20)));
+        assert_eq!(ratio("100F")?, Some((55967, 180)));
+        assert_eq!(ratio("0K")?, Some((0, 1)));
+        assert_eq!(ratio("-459.67F")?, Some((0, 1)));
+        assert_eq!(ratio("-300C")?, Some((-537, 20)));
+        assert_eq!(
+            ratio("0.0000000000000000000000000001F")?,
+            Some((
+                4596700000000000000000000000001,
+                18000000000000000000000000000
+            ))
+        );
+        assert_eq!(
+            Temp::new(Decimal::MAX, FAH).as_ratio_to_kelvin(),
+            Some((7922816251426433759354395079467, 180))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_base_unit() -> Result<(), TempError> {
+        assert_eq!(Temp::from_base_unit(dec!(273.15), CEL).to_string(), "0 C");
+        assert_eq!(Temp::from_base_unit(dec!(0), FAH).to_string(), "-459.67 F");
+
+        let body = "98.6F".parse::<Temp>()?;
+        assert_eq!(body.to_base_unit(), body.to_kelvin());
+        assert_eq!(
+            Temp::from_base_unit(body.to_base_unit().scalar, FAH),
+            Temp::new(dec!(98.6), FAH)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_linspace() -> Result<(), TempError> {
+        let (freezing, boiling) = ("0C".parse::<Temp>()?, "100C".parse::<Temp>()?);
+        let scalars = |temps: Vec<Temp>| temps.iter().map(|temp| temp.scalar).collect::<Vec<_>>();
+        assert_eq!(
+            scalars(Temp::linspace(freezing, boiling, 5)?),
+            [dec!(0), dec!(25), dec!(50), dec!(75), dec!(100)]
+        );
+        assert_eq!(
+            scalars(Temp::linspace(freezing, "212F".parse()?, 3)?),
+            [dec!(0), dec!(50), dec!(100)]
+        );
+        assert_eq!(
+            scalars(Temp::linspace(boiling, freezing, 2)?),
+            [dec!(100), dec!(0)]
+        );
+        assert_eq!(Temp::linspace(freezing, boiling, 1)?, [freezing]);
+        assert!(Temp::linspace(freezing, boiling, 0)?.is_empty());
+
+        let huge = Temp::new(Decimal::MAX, CEL);
+        assert_eq!(
+            Temp::linspace(freezing, huge, 3),
+            Err(TempError::Overflow(huge, KEL))
+        );
+        let (hot, cold) = (Temp::new(dec!(5e27), KEL), Temp::new(dec!(-5e27), KEL));
+        assert!(Temp::linspace(cold, hot, 3).is_ok());
+        assert_eq!(
+            Temp::linspace(cold, hot, 30),
+            Err(TempError::Overflow(hot, KEL))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let boiling = Temp::new(dec!(100), CEL);
+        assert_eq!(boiling, "100C");
+        assert_eq!(boiling, "212F");
+        assert_eq!(boiling, " 373.15K ");
+        assert_eq!(Temp::new(dec!(100), FAH).to_celsius(), "37.77778C");
+        assert_ne!(boiling, "212.1F");
+        assert_ne!(boiling, "hot");
+        assert_ne!(boiling, "");
+    }
+
+    #[test]
+    fn test_describe() -> Result<(), TempError> {
+        for (temp, label) in [
+            ("-0.01C", "freezing"),
+            ("0C", "cold"),
+            ("9.99C", "cold"),
+            ("10C", "mild"),
+            ("19.99C", "mild"),
+            ("20C", "warm"),
+            ("29.99C", "warm"),
+            ("30C", "hot"),
+            ("44.99C", "hot"),
+            ("45C", "scalding"),
+            ("32F", "cold"),
+            ("95F", "hot"),
+            ("0K", "freezing"),
+        ] {
+            assert_eq!(temp.parse::<Temp>()?.describe(), label, "{temp}");
+        }
+
+        let labels = [
+            ("cool", Temp::celsius(dec!(-10))),
+            ("toasty", Temp::celsius(dec!(25))),
+        ];
+        assert_eq!(Temp::celsius(dec!(-20)).describe_with(&labels), "cool");
+        assert_eq!(Temp::celsius(dec!(25)).describe_with(&labels), "toasty");
+        assert_eq!(Temp::celsius(dec!(25)).describe_with(&[]), "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_emoji() -> Result<(), TempError> {
+        assert_eq!("-5C".parse::<Temp>()?.emoji(), "🥶");
+        assert_eq!("9.99C".parse::<Temp>()?.emoji(), "🥶");
+        assert_eq!("68F".parse::<Temp>()?.emoji(), "🌡️");
+        assert_eq!("35C".parse::<Temp>()?.emoji(), "🥵");
+        assert_eq!("212F".parse::<Temp>()?.emoji(), "🥵");
+        Ok(())
+    }
+
+    #[test]
+    fn test_cmp_physical() -> Result<(), TempError> {
+        let freezing = "0C".parse::<Temp>()?;
+        let cmp = |other: &str| -> Result<Ordering, TempError> {
+            Ok(freezing.cmp_physical(other.parse()?, DEFAULT_TOLERANCE))
+        };
+        assert_eq!(cmp("32F")?, Ordering::Equal);
+        assert_eq!(cmp("32.00001F")?, Ordering::Equal);
+        assert_eq!(cmp("33F")?, Ordering::Less);
+        assert_eq!(cmp("-1C")?, Ordering::Greater);
+        assert_eq!(
+            freezing.cmp_physical("32.00001F".parse()?, dec!(0)),
+            Ordering::Less
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_approx_eq() -> Result<(), TempError> {
+        let freezing = "0C".parse::<Temp>()?;
+        assert!(freezing.approx_eq("32F".parse()?, DEFAULT_TOLERANCE));
+        assert!(freezing.approx_eq("32F".parse()?, dec!(0)));
+        assert!(!freezing.approx_eq("32.0001F".parse()?, dec!(0)));
+        assert!(freezing.approx_eq("273.15K".parse()?, dec!(0)));
+        assert!(!freezing.approx_eq("33F".parse()?, DEFAULT_TOLERANCE));
+        assert!(freezing.approx_eq("33F".parse()?, dec!(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ord() -> Result<(), TempError> {
+        assert!("0C".parse::<Temp>()? < "33F".parse()?);
+        assert!("100C".parse::<Temp>()? > "211F".parse()?);
+        assert!("-273.15C".parse::<Temp>()? < "0.001K".parse()?);
+        assert_ne!(
+            "0C".parse::<Temp>()?.cmp(&"273.15K".parse()?),
+            Ordering::Equal
+        );
+        assert_eq!(
+            "37.0C".parse::<Temp>()?.cmp(&"37C".parse()?),
+            Ordering::Equal
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_key() -> Result<(), TempError> {
+        let temps = ["100F", "0C", "310K", "-40C", "-40.5F", "0K", "37.5C", "99F"]
+            .into_iter()
+            .map(str::parse::<Temp>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut by_ord = temps.clone();
+        by_ord.sort();
+        let mut by_key = temps;
+        by_key.sort_by_key(|temp| temp.sort_key());
+        assert_eq!(by_key, by_ord);
+        assert_eq!("0C".parse::<Temp>()?.sort_key(), dec!(273.15));
+        assert_eq!("32F".parse::<Temp>()?.sort_key(), dec!(273.15));
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_conversions() -> Result<(), TempError> {
+        let boiling = "212F".parse::<Temp>()?;
+        assert_eq!(boiling.to_celsius().scalar, dec!(100));
+        assert_eq!(boiling.to_celsius_exact(), Some(Temp::new(dec!(100), CEL)));
+
+        let body = "98.6F".parse::<Temp>()?;
+        assert_eq!(body.to_celsius().scalar, dec!(37));
+        assert_eq!(body.convert_with_error(CEL)?.1, Decimal::ZERO);
+        assert_eq!("-40F".parse::<Temp>()?.to_celsius().scalar, dec!(-40));
+        assert_eq!("32.9F".parse::<Temp>()?.to_celsius().scalar, dec!(0.5));
+        assert_eq!("0F".parse::<Temp>()?.to_kelvin_exact(), None);
+        assert_eq!(
+            "255.3722K".parse::<Temp>()?.to_fahrenheit_exact(),
+            Some(Temp::new(dec!(-0.00004), FAH))
+        );
+
+        let inexact = "100F".parse::<Temp>()?;
+        assert_eq!(inexact.to_celsius_exact(), None);
+        assert_eq!(
+            boiling.try_convert_exact(CEL),
+            Ok(Temp::new(dec!(100), CEL))
+        );
+        assert_eq!(
+            inexact.try_convert_exact(CEL),
+            Err(TempError::Inexact(inexact, CEL))
+        );
+        assert_eq!(
+            inexact.to_celsius().scalar,
+            dec!(37.777777777777777777777777781)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_temps() -> Result<(), TempError> {
+        let mut temps = ["100F", "0C", "310K", "273.15K", "-40C", "32F"]
+            .into_iter()
+            .map(str::parse::<Temp>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let order = |temps: &[Temp]| temps.iter().map(Temp::to_string).collect::<Vec<_>>();
+
+        sort_temps(&mut temps, false);
+        assert_eq!(
+            order(&temps),
+            ["-40 C", "0 C", "273.15 K", "32 F", "310 K", "100 F"]
+        );
+        sort_temps(&mut temps, true);
+        assert_eq!(
+            order(&temps),
+            ["100 F", "310 K", "0 C", "273.15 K", "32 F", "-40 C"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_to() -> Result<(), TempError> {
+        assert_eq!(
+            "37.3C".parse::<Temp>()?.snap_to(dec!(0.5))?,
+            Temp::new(dec!(37.5), CEL)
+        );
+        assert_eq!(
+            "37.2C".parse::<Temp>()?.snap_to(dec!(0.5))?,
+            Temp::new(dec!(37.0), CEL)
+        );
+        assert_eq!(
+            "-12.25F".parse::<Temp>()?.snap_to(dec!(0.5))?,
+            Temp::new(dec!(-12.5), FAH)
+        );
+        assert_eq!(
+            "300K".parse::<Temp>()?.snap_to(dec!(0)),
+            Err(TempError::InvalidStep(dec!(0)))
+        );
+        assert_eq!(
+            "300K".parse::<Temp>()?.snap_to(dec!(-1)),
+            Err(TempError::InvalidStep(dec!(-1)))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rescale() {
+        let padded = Temp::new(dec!(50), CEL).rescale(3);
+        assert_eq!(padded.scalar.to_string(), "50.000");
+        assert_eq!(padded.to_string(), "50 C");
+        assert_eq!(
+            Temp::new(dec!(37.77777), CEL).rescale(2).scalar.to_string(),
+            "37.78"
+        );
+        assert_eq!(
+            Temp::new(dec!(-0.125), FAH).rescale(2).scalar.to_string(),
+            "-0.13"
+        );
+    }
+
+    #[test]
+    fn test_display_clamp() {
+        let (min, max) = (dec!(-9.9), dec!(99.9));
+        assert_eq!(
+            Temp::new(dec!(37.8), CEL).display_clamp(min, max),
+            (Temp::new(dec!(37.8), CEL), DisplayClamp::InRange)
+        );
+        assert_eq!(
+            Temp::new(dec!(-9.9), CEL).display_clamp(min, max),
+            (Temp::new(dec!(-9.9), CEL), DisplayClamp::InRange)
+        );
+        assert_eq!(
+            Temp::new(dec!(99.9), FAH).display_clamp(min, max),
+            (Temp::new(dec!(99.9), FAH), DisplayClamp::InRange)
+        );
+        assert_eq!(
+            Temp::new(dec!(-10), CEL).display_clamp(min, max),
+            (Temp::new(dec!(-9.9), CEL), DisplayClamp::BelowMin)
+        );
+        assert_eq!(
+            Temp::new(dec!(451), FAH).display_clamp(min, max),
+            (Temp::new(dec!(99.9), FAH), DisplayClamp::AboveMax)
+        );
+    }
+
+    #[test]
+    fn test_extreme_constants() {
+        let kelvins: Vec<(&str, Decimal)> = Temp::EXTREMES
+            .iter()
+            .map(|(name, temp)| (*name, temp.to_kelvin().scalar))
+            .collect();
+        assert_eq!(
+            kelvins,
+            [
+                ("absolute zero", dec!(0)),
+                ("lowest lab temperature", dec!(0.000000000038)),
+                ("cosmic microwave background", dec!(2.72548)),
+                ("core of the Sun", dec!(15700000)),
+                ("quark-gluon plasma", dec!(5500000000000)),
+            ]
+        );
+        assert_eq!(
+            Temp::QUARK_GLUON_PLASMA.to_fahrenheit(),
+            Temp::new(dec!(9899999999540.33), FAH)
+        );
+    }
+
+    #[test]
+    fn test_locale_string() -> Result<(), String> {
+        assert_eq!(
+            Temp::new(dec!(1234.5), CEL).to_locale_string(&parse_locale("de-DE")?),
+            "1.234,5 C"
+        );
+        assert_eq!(
+            Temp::new(dec!(-1234567.25), FAH).to_locale_string(&parse_locale("en-US")?),
+            "-1,234,567.25 F"
+        );
+        assert_eq!(
+            Temp::new(dec!(273.150), KEL).to_locale_string(&parse_locale("de-DE")?),
+            "273,15 K"
+        );
+        assert!(parse_locale("xx-YY").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_scales_match_formulas() {
+        for scalar in [
+            dec!(-459.67),
+            dec!(-40),
+            dec!(0),
+            dec!(32),
+            dec!(98.6),
+            dec!(1234.5678),
+        ] {
+            let (cel, fah, kel) = (
+                Temp::new(scalar, CEL),
+                Temp::new(scalar, FAH),
+                Temp::new(scalar, KEL),
+            );
+            let exact = (scalar - dec!(32)) * dec!(5) / dec!(9);
+            assert_approx_eq(fah.to_celsius(), Temp::new(exact, CEL), dec!(1e-20));
+            assert_eq!(kel.to_celsius().scalar, scalar - dec!(273.15));
+            assert_eq!(cel.to_fahrenheit().scalar, scalar * dec!(1.8) + dec!(32));
+            assert_eq!(
+                kel.to_fahrenheit().scalar,
+                scalar * dec!(1.8) - dec!(459.67)
+            );
+            assert_eq!(cel.to_kelvin().scalar, scalar + dec!(273.15));
+            let exact = (scalar + dec!(459.67)) * dec!(5) / dec!(9);
+            assert_approx_eq(fah.to_kelvin(), Temp::new(exact, KEL), dec!(1e-20));
+        }
+        assert_eq!(
+            Temp::new(dec!(-459.67), FAH).to_kelvin(),
+            Temp::ABSOLUTE_ZERO
+        );
+    }
+
+    #[test]
+    fn test_from_reading() {
+        for (raw, default_unit, strict, expected) in [
+            ("20C", None, false, Ok(Temp::new(dec!(20), CEL))),
+            ("20C", Some(FAH), false, Ok(Temp::new(dec!(20), CEL))),
+            ("20", Some(FAH), false, Ok(Temp::new(dec!(20), FAH))),
+            ("20", Some(FAH), true, Ok(Temp::new(dec!(20), FAH))),
+            ("20C", None, true, Ok(Temp::new(dec!(20), CEL))),
+            ("20", None, false, Err(TempError::InvalidUnit('0'))),
+            ("-300C", None, false, Ok(Temp::new(dec!(-300), CEL))),
+            (
+                "-300C",
+                Some(KEL),
+                true,
+                Err(TempError::BelowAbsoluteZero(Temp::new(dec!(-300), CEL))),
+            ),
+            ("-500", Some(FAH), false, Ok(Temp::new(dec!(-500), FAH))),
+            (
+                "-500",
+                Some(FAH),
+                true,
+                Err(TempError::BelowAbsoluteZero(Temp::new(dec!(-500), FAH))),
+            ),
+        ] {
+            assert_eq!(
+                Temp::from_reading(raw, default_unit, strict),
+                expected,
+                "{raw} {default_unit:?} {strict}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_assuming() {
+        assert_eq!(
+            Temp::parse_assuming(" 100 ", FAH),
+            Ok(Temp::new(dec!(100), FAH))
+        );
+        assert_eq!(
+            Temp::parse_assuming("100C", FAH),
+            Ok(Temp::new(dec!(100), CEL))
+        );
+        assert_eq!(
+            Temp::parse_assuming("15d", FAH),
+            Err(TempError::InvalidUnit('d'))
+        );
+    }
+
+    #[test]
+    fn test_map_scalar() -> Result<(), TempError> {
+        let reading = "71.5F".parse::<Temp>()?;
+        let corrected = reading.map_scalar(|scalar| scalar + dec!(0.5));
+        assert_eq!(corrected, Temp::new(dec!(72), FAH));
+        assert_eq!(corrected.unit, reading.unit);
+        assert_eq!(reading.map_scalar(|scalar| -scalar).scalar, dec!(-71.5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_abs_diff() -> Result<(), TempError> {
+        let (freezing, boiling) = ("0C".parse::<Temp>()?, "100C".parse::<Temp>()?);
+        assert_eq!(Temp::abs_diff(freezing, boiling, KEL), dec!(100));
+        assert_eq!(Temp::abs_diff(freezing, boiling, FAH), dec!(180));
+        assert_eq!(Temp::abs_diff(boiling, freezing, FAH), dec!(180));
+        assert_eq!(Temp::abs_diff(freezing, "32F".parse()?, CEL), dec!(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_delta_to() -> Result<(), TempError> {
+        let (boiling, freezing) = ("100C".parse::<Temp>()?, "0C".parse::<Temp>()?);
+        assert_eq!(boiling.delta_to(freezing), TempDelta { kelvin: dec!(100) });
+        assert_eq!(freezing.delta_to(boiling), TempDelta { kelvin: dec!(-100) });
+        assert_eq!(boiling.delta_to(freezing), boiling - freezing);
+        assert_eq!(("373.15K".parse::<Temp>()? - freezing).to_string(), "100 K");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_slope_and_offset() {
+        assert_eq!(
+            (CEL.slope(), CEL.offset(), CEL.symbol()),
+            (dec!(1), dec!(-273.15), 'C')
+        );
+        assert_eq!(
+            (FAH.slope(), FAH.offset(), FAH.symbol()),
+            (dec!(1.8), dec!(-459.67), 'F')
+        );
+        assert_eq!(
+            (KEL.slope(), KEL.offset(), KEL.symbol()),
+            (dec!(1), dec!(0), 'K')
+        );
+
+        for kelvin in [
+            dec!(0),
+            dec!(255.372),
+            dec!(273.15),
+            dec!(310.15),
+            dec!(373.15),
+        ] {
+            let temp = Temp {
+                scalar: kelvin,
+                unit: KEL,
+            };
+            for unit in [CEL, FAH, KEL] {
+                let reading = kelvin * unit.slope() + unit.offset();
+                assert_eq!(
+                    temp.convert_to(unit),
+                    Temp {
+                        scalar: reading,
+                        unit
+                    },
+                    "{temp} {unit}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_negated_delta() -> Result<(), TempError> {
+        let (warm, cold) = ("77F".parse::<Temp>()?, "-5C".parse::<Temp>()?);
+        assert_eq!(-(warm - cold), cold - warm);
+        assert_eq!(-(cold - cold), cold - cold);
+        assert_eq!((-(warm - cold)).in_unit(CEL), dec!(-30));
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaled_delta() -> Result<(), TempError> {
+        let delta = "350K".parse::<Temp>()? - "300K".parse::<Temp>()?;
+        let doubled = TempDelta::of(dec!(100), KEL);
+        assert_eq!(delta * dec!(2), doubled);
+        assert_eq!(dec!(2) * delta, doubled);
+        assert_eq!((delta * dec!(-0.5)).in_unit(FAH), dec!(-45));
+        Ok(())
+    }
+
+    #[test]
+    fn test_delta_format_in() -> Result<(), TempError> {
+        let (boiling, body) = ("100C".parse::<Temp>()?, "37C".parse::<Temp>()?);
+        assert_eq!((boiling - body).format_in(CEL), "+63 C");
+        assert_eq!((body - boiling).format_in(CEL), "-63 C");
+        assert_eq!((boiling - body).format_in(FAH), "+113.4 F");
+        assert_eq!((boiling - "98.6F".parse::<Temp>()?).format_in(KEL), "+63 K");
+        assert_eq!((body - "98.6F".parse::<Temp>()?).format_in(FAH), "+0 F");
+        assert_eq!(
+            boiling.checked_diff(body)?.checked_format_in(FAH)?,
+            "+113.4 F"
+        );
+
+        let (hottest, coldest) = (Temp::new(Decimal::MAX, KEL), Temp::new(Decimal::MIN, KEL));
+        assert_eq!(
+            hottest.checked_diff(coldest),
+            Err(TempError::DeltaOverflow(KEL))
+        );
+        assert_eq!(
+            Temp::new(Decimal::MAX, CEL).checked_diff(body),
+            Err(TempError::Overflow(Temp::new(Decimal::MAX, CEL), KEL))
+        );
+        assert_eq!(
+            hottest.checked_diff(body)?.checked_format_in(FAH),
+            Err(TempError::DeltaOverflow(FAH))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_conversion() -> Result<(), TempError> {
+        let rate = TempRate {
+            delta: TempDelta::of(dec!(2), CEL),
+            per: "minute".to_string(),
+        };
+        assert_eq!(rate.in_unit(FAH), dec!(3.6));
+        assert_eq!(rate.format_in(FAH), "3.6 F per minute");
+        assert_eq!(rate.format_in(KEL), "2 K per minute");
+        assert_eq!(TempDelta::of(dec!(3.6), FAH), rate.delta);
+        assert_eq!(rate.checked_format_in(FAH), Ok(rate.format_in(FAH)));
+        assert_eq!(TempDelta::checked_of(dec!(3.6), FAH), Ok(rate.delta));
+
+        let huge = TempRate {
+            delta: TempDelta::of(Decimal::MAX, KEL),
+            per: "second".to_string(),
+        };
+        assert_eq!(
+            huge.checked_format_in(FAH),
+            Err(TempError::DeltaOverflow(FAH))
+        );
+        assert_eq!(
+            TempDelta::checked_of(Decimal::MAX, FAH)?.checked_in_unit(FAH)?,
+            Decimal::MAX
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() -> Result<(), serde_json::Error> {
+        let temps = vec![Temp::new(dec!(212.0), FAH), Temp::new(dec!(-40), CEL)];
+        let json = serde_json::to_string(&temps)?;
+        assert_eq!(json, r#"["212F","-40C"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Temp>>(&json)?, temps);
+        assert!(serde_json::from_str::<Temp>(r#""15d""#).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_schema() -> Result<(), serde_json::Error> {
+        let schema: serde_json::Value = serde_json::from_str(&super::json_schema().to_string())?;
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["title"], "Temp");
+        assert!(schema["pattern"]
+            .as_str()
+            .is_some_and(|pattern| pattern.contains("CcFfKk")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_range() -> Result<(), TempError> {
+        let (min, max) = (
+            Some(Temp::new(dec!(0), CEL)),
+            Some(Temp::new(dec!(150), CEL)),
+        );
+        let below = Temp::new(dec!(-0.5), CEL);
+        assert_eq!(
+            below.check_range(min, max, DEFAULT_TOLERANCE),
+            Err(TempError::BelowMinimum(below, Temp::new(dec!(0), CEL)))
+        );
+        let above = Temp::new(dec!(303), FAH);
+        assert_eq!(
+            above.check_range(min, max, DEFAULT_TOLERANCE),
+            Err(TempError::AboveMaximum(above, Temp::new(dec!(150), CEL)))
+        );
+        assert_eq!(
+            above
+                .check_range(min, max, DEFAULT_TOLERANCE)
+                .unwrap_err()
+                .to_string(),
+            "303 F is above the maximum of 150 C"
+        );
+
+        for on_bound in [
+            Temp::new(dec!(0), CEL),
+            Temp::new(dec!(32), FAH),
+            Temp::new(dec!(423.15), KEL),
+            Temp::new(dec!(302), FAH),
+        ] {
+            assert_eq!(on_bound.check_range(min, max, DEFAULT_TOLERANCE)?, on_bound);
+        }
+        assert!(below.check_range(None, max, DEFAULT_TOLERANCE).is_ok());
+
+        let huge = Temp::new(Decimal::MAX, CEL);
+        assert_eq!(
+            huge.check_range(min, None, DEFAULT_TOLERANCE),
+            Err(TempError::Overflow(huge, KEL))
+        );
+        assert_eq!(huge.check_range(None, None, DEFAULT_TOLERANCE), Ok(huge));
+        let (hot, cold) = (Temp::new(dec!(5e28), KEL), Temp::new(dec!(-5e28), KEL));
+        assert_eq!(
+            cold.check_range(Some(hot), None, DEFAULT_TOLERANCE),
+            Err(TempError::BelowMinimum(cold, hot))
+        );
+        assert_eq!(
+            hot.check_range(None, Some(cold), DEFAULT_TOLERANCE),
+            Err(TempError::AboveMaximum(hot, cold))
+        );
+        assert_eq!(
+            hot.check_range(Some(cold), None, DEFAULT_TOLERANCE),
+            Ok(hot)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fraction_of() {
+        let (min, max) = (Temp::new(dec!(0), CEL), Temp::new(dec!(100), CEL));
+        assert_eq!(
+            Temp::new(dec!(50), CEL).fraction_of(min, max),
+            Some(dec!(0.5))
+        );
+        assert_eq!(
+            Temp::new(dec!(32), FAH).fraction_of(min, max),
+            Some(dec!(0))
+        );
+        assert_eq!(
+            Temp::new(dec!(398.15), KEL).fraction_of(min, max),
+            Some(dec!(1.25))
+        );
+        assert_eq!(
+            Temp::new(dec!(398.15), KEL).fraction_of_clamped(min, max),
+            Some(dec!(1))
+        );
+        assert_eq!(
+            Temp::new(dec!(-10), CEL).fraction_of_clamped(min, max),
+            Some(dec!(0))
+        );
+        assert_eq!(min.fraction_of(min, Temp::new(dec!(32), FAH)), None);
+    }
+
+    #[test]
+    fn test_convert_with_error() -> Result<(), TempError> {
+        for (temp, unit) in [
+            (Temp::new(dec!(0), CEL), FAH),
+            (Temp::new(dec!(32), FAH), CEL),
+            (Temp::new(dec!(-459.67), FAH), KEL),
+            (Temp::new(dec!(300), KEL), FAH),
+            (Temp::new(dec!(15), CEL), CEL),
+        ] {
+            let (converted, error) = temp.convert_with_error(unit)?;
+            assert_eq!(converted, temp.convert_to(unit));
+            assert_eq!(error, Decimal::ZERO, "{temp} to {unit}");
+        }
+
+        let (converted, error) = Temp::new(dec!(100), FAH).convert_with_error(CEL)?;
+        assert_eq!(converted.scalar, dec!(37.777777777777777777777777781));
+        assert!(error > Decimal::ZERO && error < dec!(0.0000000000000000000000001));
+
+        // The result fits, but checking it against the ratio doesn't
+        let huge = Temp::new(dec!(40000000000000000000000000000), KEL);
+        assert!(huge.checked_convert_to(FAH).is_ok());
+        assert_eq!(
+            huge.convert_with_error(FAH),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        let huge = Temp::new(Decimal::MAX, KEL);
+        assert_eq!(
+            huge.convert_with_error(FAH),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip_drift() {
+        assert_eq!(Temp::new(dec!(0), CEL).round_trip_drift(FAH), Decimal::ZERO);
+        assert_eq!(
+            Temp::new(dec!(300), KEL).round_trip_drift(CEL),
+            Decimal::ZERO
+        );
+
+        let lossy = Temp::new(dec!(100), FAH);
+        let drift = lossy.round_trip_drift(CEL);
+        assert_ne!(drift, Decimal::ZERO);
+        assert!(drift.abs() < DEFAULT_TOLERANCE);
+        assert!(!lossy.approx_eq(lossy.convert_to(CEL).convert_to(FAH), Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_coefficient_conversion() {
+        let per_celsius = TempCoefficient {
+            value: dec!(0.0036),
+            per: CEL,
+        };
+        let per_fahrenheit = per_celsius.convert_to(FAH);
+        assert_eq!(per_fahrenheit.value, dec!(0.002));
+        assert_eq!(per_fahrenheit.to_string(), "0.002 per F");
+        assert_eq!(per_fahrenheit.convert_to(CEL), per_celsius);
+        assert_eq!(per_celsius.convert_to(KEL).value, dec!(0.0036));
+
+        let per_kelvin = TempCoefficient {
+            value: dec!(0.004),
+            per: KEL,
+        };
+        assert_eq!(per_kelvin.convert_to(FAH).value.round_dp(6), dec!(0.002222));
+        assert_eq!(
+            per_kelvin.checked_convert_to(FAH),
+            Ok(per_kelvin.convert_to(FAH))
+        );
+
+        let huge = TempCoefficient {
+            value: Decimal::MAX,
+            per: FAH,
+        };
+        assert_eq!(
+            huge.checked_convert_to(CEL),
+            Err(TempError::Overflow(Temp::new(Decimal::MAX, FAH), CEL))
+        );
+        assert_eq!(huge.checked_convert_to(FAH), Ok(huge));
+    }
+
+    #[test]
+    fn test_spelled_out() -> Result<(), TempError> {
+        assert_eq!(format!("{:#}", "273.15K".parse::<Temp>()?), "273.15 kelvin");
+        assert_eq!(
+            format!("{:#}", "100C".parse::<Temp>()?),
+            "100 degrees Celsius"
+        );
+        assert_eq!(format!("{:#}", FAH), "degrees Fahrenheit");
+        assert_eq!(format!("{KEL}"), "K");
+        for unit in TempUnit::all() {
+            assert_eq!(
+                unit.spelled_out().starts_with("degrees "),
+                !LinearScale::of(unit).is_absolute(),
+                "{unit}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_with() -> Result<(), String> {
+        let temp = Temp::new(dec!(1234.5678), CEL);
+        assert_eq!(
+            temp.format_with(&DisplayOptions::default()),
+            temp.to_string()
+        );
+        let symbol_2dp = DisplayOptions {
+            precision: Some(2),
+            unit_style: UnitStyle::Symbol,
+            ..DisplayOptions::default()
+        };
+        assert_eq!(temp.format_with(&symbol_2dp), "1234.57 °C");
+        assert_eq!(
+            Temp::new(dec!(0.5), FAH).format_with(&symbol_2dp),
+            "0.50 °F"
+        );
+
+        let named_german = DisplayOptions {
+            precision: Some(1),
+            unit_style: UnitStyle::Name,
+            locale: Some(parse_locale("de-DE")?),
+            ..DisplayOptions::default()
+        };
+        assert_eq!(temp.format_with(&named_german), "1.234,6 degrees Celsius");
+        assert_eq!(
+            Temp::new(dec!(-40), KEL).format_with(&named_german),
+            "-40,0 kelvin"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nearest_reference() {
+        assert!(Temp::new(dec!(98.6), FAH).is_approximately(Temp::BODY_TEMPERATURE, dec!(0.01)));
+        assert_eq!(
+            Temp::new(dec!(37), CEL).nearest_reference(DEFAULT_TOLERANCE),
+            Some(("body temperature", Temp::BODY_TEMPERATURE))
+        );
+        assert_eq!(
+            Temp::new(dec!(99.5), CEL).nearest_reference(DEFAULT_TOLERANCE),
+            None
+        );
+        assert_eq!(
+            Temp::new(dec!(99.5), CEL).nearest_reference(dec!(1)),
+            Some(("boiling", Temp::WATER_BOILS))
+        );
+        // Both room and body temperature are within 10 K, but body temperature is closer
+        assert_eq!(
+            Temp::new(dec!(30), CEL).nearest_reference(dec!(10)),
+            Some(("body temperature", Temp::BODY_TEMPERATURE))
+        );
+    }
+
+    #[test]
+    fn test_try_convert_to() {
+        assert_eq!(
+            Temp::new(dec!(100), CEL).try_convert_to(FAH),
+            Ok(Temp::new(dec!(212), FAH))
+        );
+        for (temp, unit) in [
+            (Temp::new(dec!(-40), FAH), CEL),
+            (Temp::new(dec!(300), KEL), FAH),
+            (Temp::new(dec!(98.6), FAH), KEL),
+        ] {
+            assert_eq!(temp.try_convert_to(unit), Ok(temp.convert_to(unit)));
+        }
+
+        let huge = Temp::new(Decimal::MAX, KEL);
+        assert_eq!(
+            huge.try_convert_to(FAH),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        assert_eq!(
+            huge.try_convert_to(FAH).unwrap_err().to_string(),
+            "79228162514264337593543950335 K is too large to convert to F"
+        );
+        assert_eq!(huge.try_convert_to(KEL), Ok(huge));
+
+        let unphysical = Temp::new(dec!(-300), CEL);
+        assert_eq!(
+            unphysical.try_convert_to(KEL),
+            Err(TempError::BelowAbsoluteZero(unphysical))
+        );
+        assert_eq!(
+            unphysical.checked_convert_to(KEL),
+            Ok(Temp::new(dec!(-26.85), KEL))
+        );
+        assert_eq!(
+            huge.checked_convert_to(FAH),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        assert_eq!(
+            huge.check_convertible(),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        assert_eq!(unphysical.check_convertible(), Ok(unphysical));
+    }
+
+    #[test]
+    fn test_absolute_zero_guard() {
+        let nearly_zero = Temp::new(dec!(-459.669), FAH);
+        let guarded = DisplayOptions {
+            precision: Some(1),
+            absolute_zero_guard: true,
+            ..DisplayOptions::default()
+        };
+        assert_eq!(
+            nearly_zero.format_with(&DisplayOptions {
+                precision: Some(1),
+                ..DisplayOptions::default()
+            }),
+            "-459.7 F"
+        );
+        assert_eq!(nearly_zero.format_with(&guarded), "-459.6 F");
+        assert_eq!(
+            nearly_zero.format_with(&DisplayOptions {
+                precision: Some(2),
+                ..guarded
+            }),
+            "-459.67 F"
+        );
+        assert_eq!(
+            Temp::new(dec!(-273.1500000000001), CEL).format_with(&guarded),
+            "-273.2 C"
+        );
+        assert_eq!(
+            Temp::new(dec!(-273.14999999999), CEL).format_with(&DisplayOptions {
+                precision: None,
+                ..guarded
+            }),
+            "-273.15 C"
+        );
+    }
+
+    #[test]
+    fn test_keep_trailing_zeros() {
+        let boiling = Temp::new(dec!(100.00), CEL).to_fahrenheit();
+        let kept = DisplayOptions {
+            keep_trailing_zeros: true,
+            ..DisplayOptions::default()
+        };
+        assert_eq!(boiling.to_string(), "212 F");
+        assert_eq!(boiling.format_with(&kept), "212.00 F");
+        assert_eq!(
+            boiling.format_with(&DisplayOptions {
+                precision: Some(1),
+                ..kept
+            }),
+            "212.0 F"
+        );
+    }
+
+    #[test]
+    fn test_parse_loose() {
+        let boiling = Ok(Temp::new(dec!(100), CEL));
+        for typo in [
+            "100C",
+            "\"100C\"",
+            "'100c'",
+            " `100C` ",
+            "100 C",
+            "100C.",
+            "100CC",
+            "100C!?",
+            "\" 100 C. \"",
+        ] {
+            assert_eq!(Temp::parse_loose(typo), boiling, "{typo}");
+        }
+        assert_eq!(Temp::parse_loose("-40 f"), Ok(Temp::new(dec!(-40), FAH)));
+
+        assert_eq!(
+            Temp::parse_loose("100Cabbage"),
+            Err(TempError::InvalidUnitName("Cabbage".to_string()))
+        );
+        assert_eq!(Temp::parse_loose("hot C"), "hot C".parse::<Temp>());
+        assert!("100C.".parse::<Temp>().is_err());
+    }
+
+    #[test]
+    fn test_default_display_precision() {
+        let exact = Temp::new(dec!(234.63), KEL).to_celsius();
+        assert_eq!(exact.to_string(), "-38.52 C");
+        assert_eq!(
+            Temp::new(dec!(0.0000000001), KEL).to_string(),
+            "0.0000000001 K"
+        );
+
+        let long = Temp::new(dec!(100), FAH).to_celsius();
+        assert_eq!(long.scalar.to_string(), "37.777777777777777777777777781");
+        assert_eq!(long.to_string(), "37.7777777778 C");
+        assert_eq!(
+            long.format_with(&DisplayOptions {
+                precision: Some(14),
+                ..DisplayOptions::default()
+            }),
+            "37.77777777777778 C"
+        );
+        assert_eq!(
+            long.format_with(&DisplayOptions {
+                max_decimals: None,
+                ..DisplayOptions::default()
+            }),
+            "37.777777777777777777777777781 C"
+        );
+    }
+
+    #[test]
+    fn test_round_to_integer() -> Result<(), TempError> {
+        assert_eq!(
+            "98.6F"
+                .parse::<Temp>()?
+                .to_celsius()
+                .round_to_integer()
+                .to_string(),
+            "37 C"
+        );
+        assert_eq!(
+            "100F"
+                .parse::<Temp>()?
+                .to_celsius()
+                .round_to_integer()
+                .to_string(),
+            "38 C"
+        );
+        assert_eq!(
+            "36.5C".parse::<Temp>()?.round_to_integer().to_string(),
+            "36 C"
+        );
+        assert_eq!(
+            "37.5C".parse::<Temp>()?.round_to_integer().to_string(),
+            "38 C"
+        );
+        assert_eq!(
+            "-0.5C".parse::<Temp>()?.round_to_integer().to_string(),
+            "0 C"
+        );
+        let away = RoundingStrategy::MidpointAwayFromZero;
+        assert_eq!(
+            "36.5C"
+                .parse::<Temp>()?
+                .round_to_integer_with(away)
+                .to_string(),
+            "37 C"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_order() -> Result<(), String> {
+        assert_eq!(UnitOrder::default().units(), [CEL, FAH, KEL]);
+        assert_eq!("K, celsius".parse::<UnitOrder>()?.units(), [KEL, CEL]);
+        assert_eq!(
+            "K,C,k".parse::<UnitOrder>(),
+            Err("K is listed more than once".to_string())
+        );
+        assert_eq!(
+            "K,X".parse::<UnitOrder>(),
+            Err("X is not a valid temperature unit".to_string())
+        );
+        assert_eq!(
+            "K,,C".parse::<UnitOrder>(),
+            Err("Expected a comma-separated list of units, not K,,C".to_string())
+        );
+
+        let boiling = Temp {
+            scalar: dec!(100),
+            unit: CEL,
+        };
+        assert_eq!(
+            boiling.convert_all(),
+            boiling.convert_ordered(&"C,F,K".parse()?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_every_unit() -> Result<(), TempError> {
+        let freezing = "0C".parse::<Temp>()?;
+        let shown: Vec<String> = TempUnit::all()
+            .map(|unit| freezing.convert_to(unit).to_string())
+            .collect();
+        assert_eq!(shown, ["0 C", "32 F", "273.15 K"]);
+        assert_eq!(
+            TempUnit::all()
+                .map(|unit| freezing.convert_to(unit))
+                .collect::<Vec<_>>(),
+            freezing.convert_all()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_precision() -> Result<(), TempError> {
+        let body = "100F".parse::<Temp>()?.to_celsius();
+        assert_eq!(body.with_precision(1).to_string(), "37.8 C");
+        assert_eq!(body.with_precision(0).to_string(), "38 C");
+        assert_eq!(
+            "50C".parse::<Temp>()?.with_precision(2).to_string(),
+            "50.00 C"
+        );
+        assert_eq!(body.with_precision(1).temp, body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nearest() -> Result<(), TempError> {
+        let table = ["0C", "50F", "290K", "30C"]
+            .map(|temp| temp.parse::<Temp>())
+            .into_iter()
+            .collect::<Result<Vec<Temp>, _>>()?;
+        let nearest = |reading: &str| -> Result<Option<Temp>, TempError> {
+            Ok(reading.parse::<Temp>()?.nearest(&table).copied())
+        };
+        assert_eq!(nearest("12C")?, Some(table[1]));
+        assert_eq!(nearest("17C")?, Some(table[2]));
+        assert_eq!(nearest("-40F")?, Some(table[0]));
+        assert_eq!(nearest("5C")?, Some(table[0]));
+        assert_eq!(Temp::new(dec!(1), CEL).nearest(&[]), None);
+
+        let (cold, warm) = ("0C".parse::<Temp>()?, "50F".parse::<Temp>()?);
+        assert_eq!(cold.distance(warm), dec!(10));
+        assert_eq!(warm.distance(cold), dec!(10));
+        let (hot, cold) = (Temp::new(dec!(5e28), KEL), Temp::new(dec!(-5e28), KEL));
+        assert_eq!(hot.distance(cold), Decimal::MAX);
+        assert!(!hot.approx_eq(cold, DEFAULT_TOLERANCE));
+        Ok(())
+    }
+
+    #[test]
+    fn test_le_bytes() -> Result<(), TempError> {
+        for temp in ["-40.125C", "451F", "0.000000000038K", "-0C"] {
+            let temp = temp.parse::<Temp>()?;
+            let decoded = Temp::from_le_bytes(temp.to_le_bytes())?;
+            assert_eq!(decoded, temp);
+            assert_eq!(decoded.scalar.scale(), temp.scalar.scale());
+        }
+        let mut bytes = Temp::new(dec!(-1.5), FAH).to_le_bytes();
+        assert_eq!(
+            bytes,
+            [0, 0, 1, 128, 15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, b'F']
+        );
+
+        bytes[16] = b'c';
+        assert_eq!(Temp::from_le_bytes(bytes), Err(TempError::InvalidUnit('c')));
+        bytes[16] = 0xFF;
+        assert_eq!(Temp::from_le_bytes(bytes), Err(TempError::InvalidUnit('ÿ')));
+        bytes[16] = b'F';
+        bytes[2] = 29;
+        assert_eq!(
+            Temp::from_le_bytes(bytes),
+            Err(TempError::InvalidBytes(
+                "0x801d0000 are not the flags of a number".to_string()
+            ))
+        );
+        bytes[2] = 1;
+        bytes[0] = 1;
+        assert!(Temp::from_le_bytes(bytes).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_saturating_convert_to() {
+        let huge = Temp::new(Decimal::MAX, CEL);
+        assert_eq!(
+            huge.saturating_convert_to(FAH),
+            Temp::new(Decimal::MAX, FAH)
+        );
+        assert_eq!(
+            Temp::new(Decimal::MIN, CEL).saturating_convert_to(FAH),
+            Temp::new(Decimal::MIN, FAH)
+        );
+        assert_ne!(
+            Temp::new(Decimal::MIN, FAH)
+                .saturating_convert_to(KEL)
+                .scalar,
+            Decimal::MIN
+        );
+        assert_eq!(huge.saturating_convert_to(KEL).scalar, Decimal::MAX);
+        assert_eq!(
+            Temp::new(dec!(100), CEL).saturating_convert_to(FAH),
+            Temp::new(dec!(212), FAH)
+        );
+        assert_eq!(huge.saturating_convert_to(CEL), huge);
+    }
+
+    #[test]
+    fn test_millikelvin() -> Result<(), TempError> {
+        assert_eq!("0C".parse::<Temp>()?.to_millikelvin(), Some(273150));
+        assert_eq!(Temp::from_millikelvin(273150, CEL), Temp::new(dec!(0), CEL));
+        assert_eq!(Temp::from_millikelvin(273150, CEL).to_string(), "0 C");
+        assert_eq!("100F".parse::<Temp>()?.to_millikelvin(), Some(310928));
+        assert_eq!("0.0005K".parse::<Temp>()?.to_millikelvin(), Some(1));
+        assert_eq!("-0.0005K".parse::<Temp>()?.to_millikelvin(), Some(-1));
+        assert_eq!(Temp::new(Decimal::MAX, KEL).to_millikelvin(), None);
+        assert_eq!(Temp::new(Decimal::MAX, CEL).to_millikelvin(), None);
+        assert_eq!(Temp::new(dec!(1e16), KEL).to_millikelvin(), None);
+        assert_eq!(
+            Temp::from_millikelvin(i64::MAX, KEL).to_millikelvin(),
+            Some(i64::MAX)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lossless_units() -> Result<(), TempError> {
+        assert_eq!("100C".parse::<Temp>()?.lossless_units(), [CEL, FAH, KEL]);
+        assert_eq!("100F".parse::<Temp>()?.lossless_units(), [FAH]);
+        assert_eq!("212F".parse::<Temp>()?.lossless_units(), [CEL, FAH, KEL]);
+        assert_eq!("33F".parse::<Temp>()?.lossless_units(), [FAH]);
+        assert_eq!("33.8F".parse::<Temp>()?.lossless_units(), [CEL, FAH, KEL]);
+        assert_eq!(
+            "-459.67F".parse::<Temp>()?.lossless_units(),
+            [CEL, FAH, KEL]
+        );
+        for temp in ["100C", "100F", "0.00000000001K"] {
+            let temp = temp.parse::<Temp>()?;
+            for unit in temp.lossless_units() {
+                assert_eq!(temp.convert_to(unit).convert_to(temp.unit), temp);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_unit_or() -> Result<(), String> {
+        let boiling = Temp::new(dec!(100), CEL);
+        let active = "C,K".parse::<UnitOrder>()?;
+        assert_eq!(
+            boiling.to_unit_or(KEL, FAH, &active),
+            Temp::new(dec!(373.15), KEL)
+        );
+        assert_eq!(
+            boiling.to_unit_or(FAH, KEL, &active),
+            Temp::new(dec!(373.15), KEL)
+        );
+        assert_eq!(boiling.to_unit_or(FAH, CEL, &"K".parse()?), boiling);
+        assert_eq!(
+            boiling.to_unit_or(FAH, CEL, &UnitOrder::default()),
+            Temp::new(dec!(212), FAH)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_delta_arithmetic() -> Result<(), TempError> {
+        let body = "98.6F".parse::<Temp>()?;
+        let fever = TempDelta::of(dec!(2), CEL);
+        assert_eq!(body.checked_add(fever), Some("102.2F".parse()?));
+        assert_eq!(body.checked_sub(fever), Some("95F".parse()?));
+        assert_eq!(body + fever - fever, body);
+        assert_eq!(body + (fever + fever), body + fever + fever);
+        assert_eq!(fever + fever - fever, fever);
+        assert_eq!(("102.2F".parse::<Temp>()? - body) + fever, fever * dec!(2));
+
+        let hottest = Temp {
+            scalar: Decimal::MAX,
+            unit: KEL,
+        };
+        let one = TempDelta { kelvin: dec!(1) };
+        assert_eq!(hottest.checked_add(one), None);
+        assert_eq!(
+            hottest.checked_sub(one).map(|temp| temp.scalar),
+            Some(Decimal::MAX - dec!(1))
+        );
+        // Restating the difference in F overflows before the addition does
+        let huge = TempDelta {
+            kelvin: Decimal::MAX / dec!(1.5),
+        };
+        assert_eq!(body.checked_add(huge), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_value_display() {
+        let display = |scalar: Decimal| Temp { scalar, unit: CEL }.to_string();
+        let precise = |scalar: Decimal, options: DisplayOptions| {
+            Temp { scalar, unit: CEL }.format_with(&options)
+        };
+        let two_places = DisplayOptions {
+            precision: Some(2),
+            ..DisplayOptions::default()
+        };
+        let trailing_zeros = DisplayOptions {
+            keep_trailing_zeros: true,
+            ..DisplayOptions::default()
+        };
+
+        for (scalar, shown) in [
+            (dec!(0), "0 C"),
+            (dec!(-0), "0 C"),
+            (dec!(-0.000), "0 C"),
+            (Decimal::MAX, "79228162514264337593543950335 C"),
+            (Decimal::MIN, "-79228162514264337593543950335 C"),
+            (Decimal::MAX - dec!(1), "79228162514264337593543950334 C"),
+            (dec!(0.1000000000), "0.1 C"),
+            (dec!(0.00000000015), "0.0000000002 C"),
+            (dec!(0.00000000005), "0.0000000001 C"),
+            (dec!(-0.00000000005), "-0.0000000001 C"),
+            (dec!(-0.00000000004), "0 C"),
+            (dec!(0.0000000000000000000000000001), "0 C"),
+            (dec!(-0.0000000000000000000000000001), "0 C"),
+        ] {
+            assert_eq!(display(scalar), shown, "{scalar:?}");
+        }
+        assert_eq!(precise(dec!(-0.001), two_places), "0.00 C");
+        assert_eq!(
+            precise(dec!(-0.00000000004), trailing_zeros),
+            "0.0000000000 C"
+        );
+        assert_eq!(precise(dec!(-0.006), two_places), "-0.01 C");
+
+        // Long tails from the 5/9 slope stop at 10 places
+        let one = Temp {
+            scalar: dec!(1),
+            unit: FAH,
+        };
+        assert_eq!(one.to_celsius().to_string(), "-17.2222222222 C");
+        assert_eq!(one.to_kelvin().to_string(), "255.9277777778 K");
+    }
+
+    #[test]
+    fn test_parse_radix() -> Result<(), TempError> {
+        let boiling = Temp {
+            scalar: dec!(100),
+            unit: CEL,
+        };
+        assert_eq!(Temp::parse_radix("0x64C", 16)?, boiling);
+        assert_eq!(Temp::parse_radix("0X64 °C", 16)?, boiling);
+        assert_eq!(Temp::parse_radix("64c", 16)?, boiling);
+        assert_eq!(Temp::parse_radix("0b1100100C", 2)?, boiling);
+        assert_eq!(Temp::parse_radix("-0o50F", 8)?.scalar, dec!(-40));
+        assert_eq!(Temp::parse_radix("100C", 10)?, boiling);
+
+        assert_eq!(
+            Temp::parse_radix("0x6GC", 16),
+            Err(TempError::InvalidScalar("0x6G".to_string()))
+        );
+        assert_eq!(
+            Temp::parse_radix("0x-64C", 16),
+            Err(TempError::InvalidScalar("0x-64".to_string()))
+        );
+        assert_eq!(
+            Temp::parse_radix("12.5C", 16),
+            Err(TempError::InvalidScalar("12.5".to_string()))
+        );
+        assert_eq!(
+            Temp::parse_radix("0x64Q", 16),
+            Err(TempError::InvalidUnit('Q'))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_range() -> Result<(), TempError> {
+        let temps = |start: &str, end: &str, step: TempDelta| -> Result<Vec<String>, TempError> {
+            Ok(Temp::range(start.parse()?, end.parse()?, step)?
+                .map(|temp| temp.to_string())
+                .collect())
+        };
+        let five = TempDelta::of(dec!(5), CEL);
+        assert_eq!(temps("0C", "15C", five)?, ["0 C", "5 C", "10 C", "15 C"]);
+        assert_eq!(temps("0C", "14C", five)?, ["0 C", "5 C", "10 C"]);
+        assert_eq!(temps("15C", "0C", five)?, ["15 C", "10 C", "5 C", "0 C"]);
+        assert_eq!(temps("32F", "50F", five)?, ["32 F", "41 F", "50 F"]);
+        assert_eq!(temps("0C", "283.15K", five)?, ["0 C", "5 C", "10 C"]);
+        assert_eq!(temps("0C", "0C", five)?, ["0 C"]);
+        assert_eq!(
+            temps("0C", "10C", TempDelta::of(dec!(0), CEL)).err(),
+            Some(TempError::InvalidStep(dec!(0)))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_scale() -> Result<(), TempError> {
+        let raw = "985F".parse::<Temp>()?;
+        assert_eq!(raw.checked_scale(dec!(0.1)), Some("98.5F".parse()?));
+        assert_eq!(raw.checked_scale(Decimal::MAX), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_temp_and_unit() -> Result<(), TempError> {
+        fn convert<T: TryInto<Temp, Error = TempError>>(request: T) -> Result<Temp, TempError> {
+            request.try_into()
+        }
+
+        let boiling = "100C".parse::<Temp>()?;
+        let converted: Temp = (boiling, FAH).try_into()?;
+        assert_eq!(converted, "212F".parse::<Temp>()?);
+        assert_eq!(convert((boiling, KEL))?, "373.15K".parse::<Temp>()?);
+
+        let hottest = Temp {
+            scalar: Decimal::MAX,
+            unit: KEL,
+        };
+        assert_eq!(
+            convert((hottest, FAH)),
+            Err(TempError::Overflow(hottest, FAH))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trips() -> Result<(), TempError> {
+        for temp_str in [
+            "-2002F",
+            "1F",
+            "98.6F",
+            "-40.125F",
+            "0.00000000004C",
+            "255.372K",
+        ] {
+            let temp = temp_str.parse::<Temp>()?;
+            for converted in temp.convert_all() {
+                let shown = converted.to_string();
+                let parsed = shown.parse::<Temp>()?;
+                assert_eq!(
+                    parsed.to_string(),
+                    shown,
+                    "{temp_str} in {}",
+                    converted.unit
+                );
+                assert_approx_eq(parsed, converted, dec!(0.00000000005));
+            }
+        }
+        assert_eq!(
+            "-2002F".parse::<Temp>()?.to_kelvin().to_string(),
+            "-856.85 K"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_water_phase() -> Result<(), TempError> {
+        for (temp_str, phase) in [
+            ("-0.001C", WaterPhase::Solid),
+            ("0C", WaterPhase::Solid),
+            ("32F", WaterPhase::Solid),
+            ("273.15K", WaterPhase::Solid),
+            ("0.001C", WaterPhase::Liquid),
+            ("32.001F", WaterPhase::Liquid),
+            ("50C", WaterPhase::Liquid),
+            ("99.999C", WaterPhase::Liquid),
+            ("373.149K", WaterPhase::Liquid),
+            ("100C", WaterPhase::Gas),
+            ("212F", WaterPhase::Gas),
+            ("373.15K", WaterPhase::Gas),
+            ("211.999F", WaterPhase::Liquid),
+            ("1000C", WaterPhase::Gas),
+        ] {
+            assert_eq!(temp_str.parse::<Temp>()?.water_phase(), phase, "{temp_str}");
+        }
+        assert_eq!(WaterPhase::Liquid.to_string(), "liquid");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_many() -> Result<(), (usize, TempError)> {
+        assert_eq!(
+            Temp::parse_many("0C, 100C ,37C", ',')?,
+            [
+                Temp::new(dec!(0), CEL),
+                Temp::new(dec!(100), CEL),
+                Temp::new(dec!(37), CEL)
+            ]
+        );
+        assert_eq!(Temp::parse_many("32F;0K", ';')?.len(), 2);
+        assert_eq!(
+            Temp::parse_many("0C,15d,37C,10x", ','),
+            Err((1, TempError::InvalidUnit('d')))
+        );
+        assert_eq!(
+            Temp::parse_many("0C,100C,", ','),
+            Err((2, TempError::EmptyInput))
+        );
+        Ok(())
+    }
+
+    /// Evaluates the `+`, `-`, `*`, `/`, and parentheses in a formula from [`Temp::to_equation`].
+    fn evaluate(formula: &str) -> Decimal {
+        fn sum(chars: &mut std::iter::Peekable<std::str::Chars>) -> Decimal {
+            let mut total = product(chars);
+            while let Some(op) = chars.next_if(|c| matches!(c, '+' | '-')) {
+                let term = product(chars);
+                total = if op == '+' {
+                    total + term
+                } else {
+                    total - term
+                };
+            }
+            total
+        }
+        fn product(chars: &mut std::iter::Peekable<std::str::Chars>) -> Decimal {
+            let mut total = factor(chars);
+            while let Some(op) = chars.next_if(|c| matches!(c, '*' | '/')) {
+                let term = factor(chars);
+                total = if op == '*' {
+                    total * term
+                } else {
+                    total / term
+                };
+            }
+            total
+        }
+        fn factor(chars: &mut std::iter::Peekable<std::str::Chars>) -> Decimal {
+            match chars.next_if_eq(&'(') {
+                Some(_) => {
+                    let inner = sum(chars);
+                    assert_eq!(chars.next(), Some(')'));
+                    inner
+                }
+                None => {
+                    let mut number = String::from_iter(chars.next_if_eq(&'-'));
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                        number.push(c);
+                    }
+                    number.parse().expect("a number")
+                }
+            }
+        }
+
+        let mut chars = formula
+            .strip_prefix('=')
+            .expect("a leading =")
+            .chars()
+            .peekable();
+        let value = sum(&mut chars);
+        assert_eq!(chars.next(), None, "{formula}");
+        value
+    }
+
+    #[test]
+    fn test_to_equation() -> Result<(), TempError> {
+        let boiling = "100C".parse::<Temp>()?;
+        assert_eq!(boiling.to_equation(FAH), "=(100*1.8)+32");
+        assert_eq!(boiling.to_equation(KEL), "=100+273.15");
+        assert_eq!(boiling.to_equation(CEL), "=100");
+        assert_eq!("100F".parse::<Temp>()?.to_equation(CEL), "=(100-32)*5/9");
+        assert_eq!(
+            "100F".parse::<Temp>()?.to_equation(KEL),
+            "=(100+459.67)*5/9"
+        );
+        assert_eq!(
+            "100K".parse::<Temp>()?.to_equation(FAH),
+            "=(100*1.8)-459.67"
+        );
+        assert_eq!("-40F".parse::<Temp>()?.to_equation(CEL), "=(-40-32)*5/9");
+
+        for temp_str in [
+            "100C", "-40F", "98.6F", "0K", "373.15K", "-273.15C", "12.5C",
+        ] {
+            let temp = temp_str.parse::<Temp>()?;
+            for unit in TempUnit::all() {
+                let equation = temp.to_equation(unit);
+                let expected = temp.convert_to(unit).scalar;
+                assert!(
+                    (evaluate(&equation) - expected).abs() < dec!(1e-20),
+                    "{equation} isn't {expected}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invert_sign() {
+        let warm = Temp::from_celsius(dec!(25));
+        assert_eq!(warm.invert_sign().to_string(), "-25 C");
+        assert_eq!(warm.invert_sign().invert_sign(), warm);
+        assert_eq!(warm.to_fahrenheit().invert_sign().to_string(), "-77 F");
+        assert_eq!(Temp::from_kelvin(dec!(0)).invert_sign().to_string(), "0 K");
+        assert_eq!(
+            Temp::from_fahrenheit(dec!(-40)).invert_sign(),
+            Temp::new(dec!(40), FAH)
+        );
+    }
+
+    #[test]
+    fn test_scientific_display() -> Result<(), TempError> {
+        let scientific = |temp: Temp, precision: Option<u32>| {
+            temp.format_with(&DisplayOptions {
+                scientific: true,
+                precision,
+                ..DisplayOptions::default()
+            })
+        };
+        let sun = "38653675K".parse::<Temp>()?.to_fahrenheit();
+        assert_eq!(scientific(sun, Some(2)), "6.96e7 F");
+        assert_eq!(scientific(sun, None), "6.957615533e7 F");
+        assert_eq!(scientific(sun.invert_sign(), Some(3)), "-6.958e7 F");
+        assert_eq!(scientific(Temp::LOWEST_LAB_TEMPERATURE, None), "3.8e-11 K");
+        assert_eq!(
+            scientific(Temp::new(dec!(-0.00125), CEL), Some(1)),
+            "-1.3e-3 C"
+        );
+        assert_eq!(scientific(Temp::new(dec!(9.996), CEL), Some(2)), "1.00e1 C");
+        assert_eq!(scientific(Temp::new(dec!(100), CEL), None), "1e2 C");
+        assert_eq!(scientific(Temp::new(dec!(0), CEL), Some(2)), "0.00e0 C");
+        assert_eq!(
+            scientific(Temp::new(Decimal::MAX, KEL), Some(1)),
+            "7.9e28 K"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_precomputed_constants() -> Result<(), rust_decimal::Error> {
+        assert_eq!(FREEZING_KELVIN, "273.15".parse::<Decimal>()?);
+        for (scale, (absolute_zero, freezing_point)) in
+            SCALES
+                .iter()
+                .zip([("-273.15", "0"), ("-459.67", "32"), ("0", "273.15")])
+        {
+            assert_eq!(scale.absolute_zero, absolute_zero.parse::<Decimal>()?);
+            assert_eq!(scale.freezing_point, freezing_point.parse::<Decimal>()?);
+            let derived = scale.absolute_zero
+                + FREEZING_KELVIN * Decimal::from(scale.slope.1) / Decimal::from(scale.slope.0);
+            assert_eq!(scale.freezing_point, derived, "{}", scale.full_name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_parse_with_hint() -> Result<(), TempError> {
+        assert_eq!(
+            Temp::try_parse_with_hint("15d"),
+            Err(TempError::MisspelledUnit(
+                "d".to_string(),
+                vec![TempUnit::CELSIUS, TempUnit::FAHRENHEIT, TempUnit::KELVIN]
+            ))
+        );
+        assert_eq!(
+            Temp::try_parse_with_hint("15d").unwrap_err().to_string(),
+            "d is not a valid temperature unit, did you mean C, F, or K?"
+        );
+        assert_eq!(
+            Temp::try_parse_with_hint("15 celcius"),
+            Err(TempError::MisspelledUnit(
+                "celcius".to_string(),
+                vec![TempUnit::CELSIUS]
+            ))
+        );
+        assert_eq!(
+            Temp::try_parse_with_hint("15qqq"),
+            Err(TempError::InvalidUnitName("qqq".to_string()))
+        );
+        assert_eq!(
+            Temp::try_parse_with_hint("abcC"),
+            Err(TempError::InvalidScalar("abc".to_string()))
+        );
+        assert_eq!(Temp::try_parse_with_hint("15C")?, "15C".parse::<Temp>()?);
+        assert_eq!(TempUnit::suggest("kelvn"), [TempUnit::KELVIN]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scientific_parts() -> Result<(), TempError> {
+        // Only positive powers of ten are needed to join these back together
+        let join = |(mantissa, exponent): (Decimal, i32)| {
+            (0..exponent).fold(mantissa, |scalar, _| scalar * Decimal::TEN)
+        };
+        let sun = "38573100F".parse::<Temp>()?;
+        assert_eq!(sun.to_scientific_parts(FAH), (dec!(3.85731), 7));
+        assert_eq!(join(sun.to_scientific_parts(FAH)), sun.scalar);
+
+        let (mantissa, exponent) = sun.to_scientific_parts(CEL);
+        assert_eq!(join((mantissa, exponent)), sun.to_celsius().scalar);
+        assert_eq!(exponent, 7);
+        assert!((Decimal::ONE..Decimal::TEN).contains(&mantissa));
+
+        assert_eq!(
+            Temp::new(dec!(-0.00125), CEL).to_scientific_parts(CEL),
+            (dec!(-1.25), -3)
+        );
+        assert_eq!(
+            Temp::new(dec!(0.00), CEL).to_scientific_parts(CEL),
+            (dec!(0), 0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_guess_units() {
+        let guesses = Temp::guess_units(dec!(98.6), CEL);
+        assert_eq!(
+            guesses[0],
+            (Temp::new(dec!(98.6), FAH), Temp::new(dec!(37), CEL))
+        );
+        assert_eq!(
+            guesses[1..],
+            [
+                (Temp::new(dec!(98.6), CEL), Temp::new(dec!(98.6), CEL)),
+                (Temp::new(dec!(98.6), KEL), Temp::new(dec!(-174.55), CEL)),
+            ]
+        );
+
+        let guesses = Temp::guess_units(dec!(300), FAH);
+        assert_eq!(guesses[0].0, Temp::new(dec!(300), KEL));
+        assert_eq!(Temp::guess_units(Decimal::MAX, KEL).len(), 1);
+    }
+
+    #[test]
+    fn test_as_ref_scalar() -> Result<(), TempError> {
+        let temp = "212F".parse::<Temp>()?;
+        let scalar = AsRef::<Decimal>::as_ref(&temp);
+        assert_eq!(scalar, &temp.scalar);
+        assert_ne!(temp.to_celsius().as_ref(), scalar);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_string_rounded() -> Result<(), TempError> {
+        let hot = "100F".parse::<Temp>()?.to_celsius();
+        assert_eq!(hot.to_string(), "37.7777777778 C");
+        assert_eq!(hot.to_string_rounded(2), "37.78 C");
+        assert_eq!(hot.to_string_rounded(0), "38 C");
+        assert_eq!(
+            "98.6F".parse::<Temp>()?.to_celsius().to_string_rounded(2),
+            "37 C"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_table() -> Result<(), TempError> {
+        // Each expected value only counts to the places the table writes it with, so it is
+        // allowed to be off by half of its last place. That covers the table's own rounding of
+        // results that go through 5/9, which is far coarser than any rounding in the conversion.
+        let table = include_str!("../testdata/conversions.csv");
+        let mut checked = 0;
+        for line in table.lines().filter(|line| !line.starts_with('#')).skip(1) {
+            let mut fields = line.split(',');
+            let (Some(input), Some(target), Some(expected)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                panic!("{line} should have an input, target, and expected value");
+            };
+            let (input, target) = (input.parse::<Temp>()?, TempUnit::parse(target)?);
+            let expected = expected
+                .parse::<Decimal>()
+                .map_err(|_| TempError::InvalidScalar(expected.to_string()))?;
+            let tolerance = Decimal::new(5, expected.scale() + 1);
+
+            let converted = input.convert_to(target);
+            assert_eq!(converted.unit, target, "{line}");
+            assert!(
+                (converted.scalar - expected).abs() <= tolerance,
+                "{line}: converted to {converted}"
+            );
+            checked += 1;
+        }
+        assert_eq!(checked, 54);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_corpus() -> Result<(), Box<dyn std::error::Error>> {
+        let corpus = include_str!("../testdata/parser_corpus.jsonl");
+        for line in corpus.lines().filter(|line| !line.starts_with('#')) {
+            let case: serde_json::Value = serde_json::from_str(line)?;
+            let input = case["input"]
+                .as_str()
+                .ok_or(format!("{line} has no input"))?;
+            let parsed = input.parse::<Temp>();
+            match (case["temp"].as_str(), case["error"].as_str()) {
+                (Some(expected), None) => {
+                    let (scalar, unit) = expected
+                        .split_once(' ')
+                        .ok_or(format!("{line} should be written like 20 C"))?;
+                    let expected = Temp::new(scalar.parse()?, TempUnit::parse(unit)?);
+                    assert_eq!(parsed, Ok(expected), "{input:?}");
+                }
+                (None, Some(kind)) => {
+                    assert_eq!(parsed.map_err(|err| err.kind()), Err(kind), "{input:?}")
+                }
+                _ => panic!("{line} should have either a temp or an error"),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_can_convert_to() {
+        let huge = Temp::new(Decimal::MAX - dec!(1), KEL);
+        assert!(!huge.can_convert_to(FAH));
+        assert!(huge.can_convert_to(CEL));
+        assert!(huge.can_convert_to(KEL));
+        assert!(Temp::new(dec!(-500), CEL).can_convert_to(FAH));
+    }
+
+    #[test]
+    fn test_interpolate() -> Result<(), TempError> {
+        let (a, b) = ("0C".parse::<Temp>()?, "212F".parse::<Temp>()?);
+        for (index, temp) in Temp::linspace(a, b, 5)?.into_iter().enumerate() {
+            let t = Decimal::from(index) / dec!(4);
+            assert_eq!(Temp::interpolate(a, b, t, Easing::Linear), temp);
+        }
+
+        let half = dec!(0.5);
+        let linear = Temp::interpolate(a, b, half, Easing::Linear);
+        assert_eq!(linear, Temp::new(dec!(50), CEL));
+        let eased_in = Temp::interpolate(a, b, half, Easing::EaseIn);
+        assert!(eased_in.distance(a) < linear.distance(a));
+        assert_eq!(eased_in, Temp::new(dec!(25), CEL));
+        assert_eq!(
+            Temp::interpolate(a, b, half, Easing::EaseOut),
+            Temp::new(dec!(75), CEL)
+        );
+        assert_eq!(Temp::interpolate(a, b, half, Easing::EaseInOut), linear);
+        assert_eq!(
+            Temp::interpolate(a, b, dec!(0.25), Easing::EaseInOut),
+            Temp::new(dec!(12.5), CEL)
+        );
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(Decimal::ZERO), Decimal::ZERO);
+            assert_eq!(easing.apply(Decimal::ONE), Decimal::ONE);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_absolute() {
+        assert!(TempUnit::KELVIN.is_absolute());
+        assert!(!TempUnit::CELSIUS.is_absolute());
+        assert!(!TempUnit::FAHRENHEIT.is_absolute());
+        assert_eq!(
+            TempUnit::all()
+                .filter(|unit| unit.is_absolute())
+                .collect::<Vec<_>>(),
+            [TempUnit::BASE]
+        );
+    }
+
+    #[test]
+    fn test_display_drift() -> Result<(), TempError> {
+        let options = DisplayOptions::default();
+        let hot = "100F".parse::<Temp>()?;
+        assert_eq!(
+            hot.to_celsius().display_drift(hot, &options),
+            dec!(0.00000000004)
+        );
+        let rounded = DisplayOptions {
+            precision: Some(2),
+            ..options
+        };
+        assert_eq!(hot.to_celsius().display_drift(hot, &rounded), dec!(0.004));
+
+        let boiling = "212F".parse::<Temp>()?;
+        assert_eq!(
+            boiling.to_celsius().display_drift(boiling, &options),
+            dec!(0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_delta() -> Result<(), TempError> {
+        let five_celsius = "5ΔC".parse::<TempDelta>()?;
+        assert_eq!(five_celsius, "9dF".parse::<TempDelta>()?);
+        assert_eq!(five_celsius.in_unit(FAH), dec!(9));
+        assert_eq!(five_celsius, TempDelta::of(dec!(5), KEL));
+        assert_eq!(
+            " -9 Δ°F ".parse::<TempDelta>()?,
+            TempDelta::of(dec!(-5), CEL)
+        );
+
+        assert_eq!(
+            "5C".parse::<TempDelta>(),
+            Err(TempError::InvalidDelta("5C".to_string()))
+        );
+        assert_eq!(
+            "5dC".parse::<Temp>(),
+            Err(TempError::InvalidScalar("5d".to_string()))
+        );
+        assert_eq!(
+            "warmdC".parse::<TempDelta>(),
+            Err(TempError::InvalidScalar("warm".to_string()))
+        );
+        assert_eq!("5dX".parse::<TempDelta>(), Err(TempError::InvalidUnit('X')));
+        assert_eq!("".parse::<TempDelta>(), Err(TempError::EmptyInput));
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_modes() {
+        let options = |rounding| DisplayOptions {
+            precision: Some(2),
+            rounding,
+            ..DisplayOptions::default()
+        };
+        for (scalar, away, even, toward_zero) in [
+            (dec!(0.125), "0.13", "0.12", "0.12"),
+            (dec!(0.135), "0.14", "0.14", "0.13"),
+            (dec!(-0.125), "-0.13", "-0.12", "-0.12"),
+            (dec!(0.129), "0.13", "0.13", "0.12"),
+            (dec!(2), "2.00", "2.00", "2.00"),
+        ] {
+            let temp = Temp::new(scalar, CEL);
+            assert_eq!(
+                temp.format_scalar(&options(RoundMode::HalfAwayFromZero)),
+                away
+            );
+            assert_eq!(temp.format_scalar(&options(RoundMode::HalfEven)), even);
+            assert_eq!(
+                temp.format_scalar(&options(RoundMode::TowardZero)),
+                toward_zero
+            );
+        }
+
+        let scientific = DisplayOptions {
+            scientific: true,
+            ..options(RoundMode::HalfEven)
+        };
+        assert_eq!(
+            Temp::new(dec!(1125), CEL).format_scalar(&scientific),
+            "1.12e3"
+        );
+        assert_eq!(
+            Temp::new(dec!(0.125), CEL).rescale_with(2, RoundMode::HalfEven),
+            Temp::new(dec!(0.12), CEL)
+        );
+        assert_eq!(
+            DisplayOptions::default().rounding,
+            RoundMode::HalfAwayFromZero
+        );
+    }
+
+    #[test]
+    fn test_canonical_string() -> Result<(), TempError> {
+        let freezing = "0C".parse::<Temp>()?.canonical_string();
+        assert_eq!(freezing, "273.1500K");
+        assert_eq!("32F".parse::<Temp>()?.canonical_string(), freezing);
+        assert_eq!("273.15K".parse::<Temp>()?.canonical_string(), freezing);
+        assert_eq!("100F".parse::<Temp>()?.canonical_string(), "310.9278K");
+        assert_eq!(
+            "100F".parse::<Temp>()?.to_celsius().canonical_string(),
+            "310.9278K"
+        );
+        assert_eq!(Temp::new(dec!(-0.00001), KEL).canonical_string(), "0.0000K");
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaled_kelvin() -> Result<(), TempError> {
+        let room = "20C".parse::<Temp>()?;
+        assert_eq!(Temp::from_centikelvin(29315, CEL), room);
+        assert_eq!(room.to_centikelvin(), Some(29315));
+        assert_eq!(Temp::from_centikelvin(-1, KEL).to_centikelvin(), Some(-1));
+        assert_eq!("0.005K".parse::<Temp>()?.to_centikelvin(), Some(1));
+
+        assert_eq!(Temp::from_scaled_kelvin(29315, 2, CEL)?, room);
+        assert_eq!(Temp::from_scaled_kelvin(293, 0, KEL)?.to_string(), "293 K");
+        assert_eq!(room.to_scaled_kelvin(0), Some(293));
+        assert_eq!(room.to_scaled_kelvin(6), Some(293_150_000));
+        for (scale, unit) in [(0, FAH), (1, CEL), (4, FAH), (9, FAH), (28, KEL)] {
+            let temp = Temp::from_scaled_kelvin(-4_815_162_342, scale, unit)?;
+            assert_eq!(
+                temp.to_scaled_kelvin(scale),
+                Some(-4_815_162_342),
+                "{scale}"
+            );
+        }
+        assert_eq!(
+            Temp::from_scaled_kelvin(1, 29, KEL),
+            Err(TempError::InvalidScale(29))
+        );
+        assert_eq!(room.to_scaled_kelvin(29), None);
+        assert_eq!(Temp::new(Decimal::MAX, KEL).to_scaled_kelvin(0), None);
+
+        assert_eq!(
+            Temp::checked_from_millikelvin(273150, CEL, true)?,
+            Temp::from_millikelvin(273150, CEL)
+        );
+        let below = Temp::from_millikelvin(-1, KEL);
+        assert_eq!(Temp::checked_from_millikelvin(-1, KEL, false), Ok(below));
+        assert_eq!(
+            Temp::checked_from_millikelvin(-1, KEL, true),
+            Err(TempError::BelowAbsoluteZero(below))
+        );
+        assert_eq!(
+            Temp::checked_from_scaled_kelvin(-1, 30, KEL, true),
+            Err(TempError::InvalidScale(30))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_scalar() {
+        let setpoint = Temp::from_celsius(dec!(20)).with_scalar(dec!(25));
+        assert_eq!(setpoint, Temp::new(dec!(25), CEL));
+        assert_eq!(setpoint.to_string(), "25 C");
+        assert_eq!(
+            Temp::from_fahrenheit(dec!(-40)).with_scalar(dec!(451)).unit,
+            FAH
+        );
+    }
+
+    #[test]
+    fn test_explain_json() -> Result<(), Box<dyn std::error::Error>> {
+        let explained = "212F".parse::<Temp>()?.explain_json(CEL)?;
+        assert_eq!(
+            explained,
+            serde_json::json!({
+                "formula": "(F-32)*5/9",
+                "steps": [
+                    {
+                        "operation": "subtract",
+                        "constant": "32",
+                        "value": "180",
+                        "description": "Subtract 32, since F reads 32 at the freezing point of water",
+                    },
+                    {
+                        "operation": "multiply",
+                        "constant": "5/9",
+                        "value": "100",
+                        "description": "Multiply by 5/9, the size of a F degree in C degrees",
+                    },
+                ],
+                "result": {"scalar": "100", "unit": "C"},
+            })
+        );
+        assert_eq!(
+            "0C".parse::<Temp>()?.explain_json(KEL)?["steps"][0]["description"],
+            "Add 273.15, since C reads -273.15 at absolute zero"
+        );
+
+        for original in ["-40C", "98.6F", "100F", "0K", "300K"] {
+            let original = original.parse::<Temp>()?;
+            for unit in TempUnit::all() {
+                let text = original.explain_json(unit)?.to_string();
+                let explained: serde_json::Value = serde_json::from_str(&text)?;
+                let result = &explained["result"];
+                let converted = original.convert_to(unit);
+                assert_eq!(result["unit"], unit.to_string(), "{original} to {unit}");
+                assert_eq!(
+                    result["scalar"].as_str().map(str::parse::<Decimal>),
+                    Some(Ok(converted.scalar)),
+                    "{original} to {unit}"
+                );
+                let steps = explained["steps"].as_array().ok_or("steps is an array")?;
+                match steps.last() {
+                    Some(last) => assert_eq!(last["value"], result["scalar"]),
+                    None => assert_eq!(unit, original.unit),
+                }
+                assert_eq!(
+                    explained["formula"].as_str().map(|formula| formula
+                        .replace(&original.unit.to_string(), &original.scalar.to_string())),
+                    Some(original.to_equation(unit)[1..].to_string())
+                );
+            }
+        }
+
+        let huge = Temp::new(Decimal::MAX, KEL);
+        assert_eq!(huge.explain_json(FAH), Err(TempError::Overflow(huge, FAH)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantize_to_resolution() -> Result<(), TempError> {
+        let reading = "21.3C".parse::<Temp>()?;
+        assert_eq!(
+            reading.quantize_to_resolution(dec!(0.0625)),
+            Temp::new(dec!(21.3125), CEL)
+        );
+        assert_eq!(
+            Resolution::DS18B20.quantize(reading)?,
+            "21.3125C".parse::<Temp>()?
+        );
+        assert_eq!(
+            Resolution::DS18B20.quantize("21.28C".parse::<Temp>()?)?,
+            "21.25C".parse::<Temp>()?
+        );
+        // 70.34 F is 21.3 C, which the DS18B20 reads as 21.3125 C
+        assert_eq!(
+            Resolution::DS18B20.quantize("70.34F".parse::<Temp>()?)?,
+            Temp::new(dec!(70.3625), FAH)
+        );
+        assert_eq!(
+            Resolution::TMP117.quantize(reading)?.scalar,
+            dec!(21.296875)
+        );
+        assert_eq!(reading.quantize_to_resolution(dec!(0)), reading);
+        let huge = Temp::new(Decimal::MAX, KEL);
+        let fahrenheit = Resolution {
+            step: dec!(0.1),
+            unit: FAH,
+        };
+        assert_eq!(
+            fahrenheit.quantize(huge),
+            Err(TempError::Overflow(huge, FAH))
+        );
+        assert_eq!(Resolution::DS18B20.quantize(huge), Ok(huge));
+
+        assert_eq!("DS18B20".parse(), Ok(Resolution::DS18B20));
+        assert_eq!(
+            "lm35".parse::<Resolution>(),
+            Err("lm35 is not a known sensor, expected one of ds18b20, tmp117, sht3x".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordered_kelvin_bits() -> Result<(), TempError> {
+        let temps = [
+            "100F",
+            "0C",
+            "310K",
+            "-40C",
+            "-40.5F",
+            "0K",
+            "37.5C",
+            "99F",
+            "-5K",
+            "-500F",
+            "32F",
+            "0.0000000001K",
+            "-0.0000000001K",
+            "1000000000000C",
+            "-1000000000000F",
+        ]
+        .into_iter()
+        .map(str::parse::<Temp>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut by_physical = temps.clone();
+        by_physical.sort_by(|a, b| a.cmp_physical(*b, Decimal::ZERO));
+        let mut by_bits = temps.clone();
+        by_bits.sort_by_key(|temp| temp.to_ordered_kelvin_bits());
+        assert_eq!(
+            by_bits
+                .iter()
+                .map(|temp| temp.sort_key())
+                .collect::<Vec<_>>(),
+            by_physical
+                .iter()
+                .map(|temp| temp.sort_key())
+                .collect::<Vec<_>>()
+        );
+        assert!(temps
+            .iter()
+            .all(|temp| temp.to_ordered_kelvin_bits().is_some()));
+
+        let freezing = "0C".parse::<Temp>()?.to_ordered_kelvin_bits();
+        assert_eq!(freezing, "32F".parse::<Temp>()?.to_ordered_kelvin_bits());
+        let zero = Temp::new(dec!(0), KEL).to_ordered_kelvin_bits();
+        assert_eq!(
+            zero,
+            Some([0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            Temp::new(dec!(-0.0000000001), KEL).to_ordered_kelvin_bits(),
+            Some([
+                0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff
+            ])
         );
+        assert_eq!(Temp::new(Decimal::MAX, KEL).to_ordered_kelvin_bits(), None);
         Ok(())
     }
 }
@@ -0,0 +1,402 @@
+use std::str::FromStr;
+
+use crate::error::TempError;
+use crate::temp::{DisplayOptions, Temp};
+
+/// The sentence printed when no template is given.
+pub const DEFAULT_TEMPLATE: &str =
+    "{input_scalar} {input_unit} is equal to {output_scalar} {output_unit}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    InputScalar,
+    InputUnit,
+    OutputScalar,
+    OutputUnit,
+}
+
+impl FromStr for Placeholder {
+    type Err = TempError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "input_scalar" => Ok(Self::InputScalar),
+            "input_unit" => Ok(Self::InputUnit),
+            "output_scalar" => Ok(Self::OutputScalar),
+            "output_unit" => Ok(Self::OutputUnit),
+            _ => Err(TempError::InvalidTemplate(format!(
+                "{{{name}}} is not a known placeholder"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Value(Placeholder),
+    /// How far the output drifts from the input when converted back, if at all
+    DriftNote,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+/// How far to pad a column of scalars so their decimal points line up: the widest part before
+/// the decimal separator, and the widest part from it on.
+struct Alignment {
+    whole: usize,
+    fraction: usize,
+}
+
+impl Alignment {
+    /// The widths that fit every one of `scalars`, already formatted with `separator`.
+    fn fitting(scalars: impl Iterator<Item = String>, separator: &str) -> Self {
+        scalars.fold(Self::default(), |widest, scalar| {
+            let (whole, fraction) = split_fraction(&scalar, separator);
+            Alignment {
+                whole: widest.whole.max(whole.chars().count()),
+                fraction: widest.fraction.max(fraction.chars().count()),
+            }
+        })
+    }
+
+    /// Pads `scalar` with spaces on both sides to fill the column.
+    fn pad(&self, scalar: String, separator: &str) -> String {
+        let (whole, fraction) = split_fraction(&scalar, separator);
+        format!(
+            "{:>whole_width$}{:<fraction_width$}",
+            whole,
+            fraction,
+            whole_width = self.whole,
+            fraction_width = self.fraction
+        )
+    }
+}
+
+/// Splits a scalar into the part before its decimal separator and the rest, separator included.
+fn split_fraction<'a>(scalar: &'a str, separator: &str) -> (&'a str, &'a str) {
+    scalar.split_at(scalar.find(separator).unwrap_or(scalar.len()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A user-defined layout for a conversion result, such as
+/// `{input_scalar}{input_unit} -> {output_scalar}{output_unit}`. Literal braces are written as
+/// `{{` and `}}`.
+pub struct Template {
+    segments: Vec<Segment>,
+    /// How the input and output scalars are padded, which is not at all unless aligned
+    alignment: (Alignment, Alignment),
+}
+
+impl Template {
+    /// Fills in the placeholders from a conversion, formatting values as `options` describe.
+    pub fn render(&self, input: Temp, output: Temp, options: &DisplayOptions) -> String {
+        let separator = decimal_separator(options);
+        let (input_alignment, output_alignment) = self.alignment;
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Value(Placeholder::InputScalar) => {
+                    input_alignment.pad(input.format_scalar(options), separator)
+                }
+                Segment::Value(Placeholder::InputUnit) => input.format_unit(options),
+                Segment::Value(Placeholder::OutputScalar) => {
+                    output_alignment.pad(output.format_scalar(options), separator)
+                }
+                Segment::Value(Placeholder::OutputUnit) => output.format_unit(options),
+                Segment::DriftNote => match output.display_drift(input, options) {
+                    drift if drift.is_zero() => String::new(),
+                    drift => format!(" (±{} {})", drift.abs(), input.format_unit(options)),
+                },
+            })
+            .collect()
+    }
+
+    /// A copy of the template that lines up the scalars of every one of `conversions` (input and
+    /// output pairs) in columns, when they're formatted as `options` describe. Whole parts are
+    /// right-aligned, and the decimal separators sit one above the other. The widths have to be
+    /// known up front, so every conversion must be done before any are rendered.
+    pub fn aligned_to(&self, conversions: &[(Temp, Temp)], options: &DisplayOptions) -> Self {
+        let separator = decimal_separator(options);
+        let fitting = |scalars: &dyn Fn(&(Temp, Temp)) -> Temp| {
+            Alignment::fitting(
+                conversions
+                    .iter()
+                    .map(|conversion| scalars(conversion).format_scalar(options)),
+                separator,
+            )
+        };
+        Template {
+            segments: self.segments.clone(),
+            alignment: (
+                fitting(&|(input, _)| *input),
+                fitting(&|(_, output)| *output),
+            ),
+        }
+    }
+}
+
+impl Template {
+    /// A copy of the template that writes `prefix` before and `suffix` after each result. Both are
+    /// plain text, so braces in them are written as they are.
+    pub fn wrapped(&self, prefix: &str, suffix: &str) -> Self {
+        let mut segments = Vec::with_capacity(self.segments.len() + 2);
+        segments.push(Segment::Literal(prefix.to_string()));
+        segments.extend(self.segments.iter().cloned());
+        segments.push(Segment::Literal(suffix.to_string()));
+        Template {
+            segments,
+            alignment: self.alignment,
+        }
+    }
+}
+
+impl Template {
+    /// A copy of the template that ends each result with how far the output, as written, lands
+    /// from the input once converted back (ex. 37.7777777778 C (±0.00000000004 F) for 100 F).
+    /// Results that convert back exactly are left as they are. See [`Temp::display_drift`].
+    pub fn with_drift_note(&self) -> Self {
+        let mut segments = self.segments.clone();
+        segments.push(Segment::DriftNote);
+        Template {
+            segments,
+            alignment: self.alignment,
+        }
+    }
+}
+
+/// The decimal separator that scalars are formatted with.
+fn decimal_separator(options: &DisplayOptions) -> &'static str {
+    options.locale.map_or(".", |locale| locale.decimal())
+}
+
+impl Template {
+    /// The bare converted number, optionally followed by its unit letter (ex. 212 or 212F), for
+    /// output that other programs read.
+    pub fn pipe(with_unit: bool) -> Self {
+        let mut segments = vec![Segment::Value(Placeholder::OutputScalar)];
+        if with_unit {
+            segments.push(Segment::Value(Placeholder::OutputUnit));
+        }
+        Template {
+            segments,
+            alignment: Default::default(),
+        }
+    }
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        DEFAULT_TEMPLATE
+            .parse()
+            .expect("the default template is valid")
+    }
+}
+
+impl FromStr for Template {
+    type Err = TempError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    literal.push(c);
+                    chars.next();
+                }
+                ('{', _) => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        return Err(TempError::InvalidTemplate(format!(
+                            "{{{name} is never closed"
+                        )));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Value(name.parse()?));
+                }
+                ('}', _) => {
+                    return Err(TempError::InvalidTemplate(
+                        "} must be written as }} outside of a placeholder".to_string(),
+                    ))
+                }
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self {
+            segments,
+            alignment: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use crate::error::TempError;
+    use crate::temp::{DisplayOptions, Temp};
+    use num_format::Locale;
+
+    fn boiling() -> Result<(Temp, Temp), TempError> {
+        let input = "100C".parse::<Temp>()?;
+        Ok((input, input.to_fahrenheit()))
+    }
+
+    #[test]
+    fn test_default_template() -> Result<(), TempError> {
+        let (input, output) = boiling()?;
+        assert_eq!(
+            Template::default().render(input, output, &DisplayOptions::default()),
+            "100 C is equal to 212 F"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_templates() -> Result<(), TempError> {
+        let (input, output) = boiling()?;
+        assert_eq!(
+            "{input_scalar}{input_unit} -> {output_scalar}{output_unit}"
+                .parse::<Template>()?
+                .render(input, output, &DisplayOptions::default()),
+            "100C -> 212F"
+        );
+        assert_eq!(
+            "{{{output_scalar}}} {{unit}}: {output_unit}"
+                .parse::<Template>()?
+                .render(input, output, &DisplayOptions::default()),
+            "{212} {unit}: F"
+        );
+        assert_eq!(
+            "{input_scalar} {input_unit}".parse::<Template>()?.render(
+                "1234.5K".parse()?,
+                output,
+                &DisplayOptions {
+                    locale: Some(Locale::de),
+                    ..DisplayOptions::default()
+                }
+            ),
+            "1.234,5 K"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipe_template() -> Result<(), TempError> {
+        let (input, output) = boiling()?;
+        let options = DisplayOptions::default();
+        assert_eq!(Template::pipe(false).render(input, output, &options), "212");
+        assert_eq!(Template::pipe(true).render(input, output, &options), "212F");
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(matches!(
+            "{input_scalar} in {kelvin}".parse::<Template>(),
+            Err(TempError::InvalidTemplate(_))
+        ));
+        assert!(matches!(
+            "{input_scalar".parse::<Template>(),
+            Err(TempError::InvalidTemplate(_))
+        ));
+        assert!(matches!(
+            "input} ".parse::<Template>(),
+            Err(TempError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_aligned_template() -> Result<(), TempError> {
+        let conversions = ["0C", "37.5C", "-40C", "1000.125C"]
+            .iter()
+            .map(|temp| {
+                let input = temp.parse::<Temp>()?;
+                Ok((input, input.to_fahrenheit()))
+            })
+            .collect::<Result<Vec<_>, TempError>>()?;
+        let options = DisplayOptions::default();
+        let template = Template::default().aligned_to(&conversions, &options);
+        let lines: Vec<String> = conversions
+            .iter()
+            .map(|(input, output)| template.render(*input, *output, &options))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "   0     C is equal to   32     F",
+                "  37.5   C is equal to   99.5   F",
+                " -40     C is equal to  -40     F",
+                "1000.125 C is equal to 1832.225 F",
+            ]
+        );
+
+        let options = DisplayOptions {
+            locale: Some(Locale::de),
+            ..options
+        };
+        let template = Template::pipe(false).aligned_to(&conversions[..2], &options);
+        assert_eq!(
+            template.render(conversions[0].0, conversions[0].1, &options),
+            "32  "
+        );
+        assert_eq!(
+            template.render(conversions[1].0, conversions[1].1, &options),
+            "99,5"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_template() -> Result<(), TempError> {
+        let (input, output) = boiling()?;
+        let options = DisplayOptions::default();
+        assert_eq!(
+            Template::default()
+                .wrapped("Temp: ", " (converted)")
+                .render(input, output, &options),
+            "Temp: 100 C is equal to 212 F (converted)"
+        );
+        assert_eq!(
+            Template::pipe(true)
+                .wrapped("{", "}")
+                .render(input, output, &options),
+            "{212F}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_drift_note() -> Result<(), TempError> {
+        let options = DisplayOptions::default();
+        let template = Template::default().with_drift_note();
+        let (input, output) = boiling()?;
+        assert_eq!(
+            template.render(input, output, &options),
+            "100 C is equal to 212 F"
+        );
+
+        let input = "100F".parse::<Temp>()?;
+        assert_eq!(
+            template.render(input, input.to_celsius(), &options),
+            "100 F is equal to 37.7777777778 C (±0.00000000004 F)"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs::{File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Follows a file that lines are appended to, like `tail -f`. The file is reopened on every
+/// poll, so being truncated or replaced by a new file (ex. by log rotation) starts reading from
+/// the top again.
+pub struct Tail {
+    path: PathBuf,
+    position: u64,
+    /// Which file `position` is in, where the platform can tell files apart
+    identity: Option<u64>,
+    /// The end of the last line read so far, if it hasn't been finished with a newline yet
+    partial: String,
+}
+
+impl Tail {
+    /// Starts following `path` from its current end, so only lines appended from now on are read.
+    /// A file that doesn't exist yet is read from the top once it's created.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let metadata = path.metadata().ok();
+        Tail {
+            position: metadata.as_ref().map_or(0, Metadata::len),
+            identity: metadata.as_ref().and_then(identity),
+            path,
+            partial: String::new(),
+        }
+    }
+
+    /// The lines that have been finished since the last poll, without their line endings.
+    pub fn poll(&mut self) -> io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            // Between a rotation's rename and the new file being created
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.restart(None);
+                return Ok(Vec::new());
+            }
+            Err(err) => return Err(err),
+        };
+        let metadata = file.metadata()?;
+        if metadata.len() < self.position || identity(&metadata) != self.identity {
+            self.restart(identity(&metadata));
+        }
+
+        file.seek(SeekFrom::Start(self.position))?;
+        let mut appended = String::new();
+        self.position += file.read_to_string(&mut appended)? as u64;
+        self.partial.push_str(&appended);
+
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let finished: Vec<String> = self.partial[..=end]
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+        self.partial.drain(..=end);
+        Ok(finished)
+    }
+
+    fn restart(&mut self, identity: Option<u64>) {
+        self.position = 0;
+        self.identity = identity;
+        self.partial.clear();
+    }
+}
+
+#[cfg(unix)]
+fn identity(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn identity(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tail;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+
+    #[test]
+    fn test_tail_follows_appends() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("cfk-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("sensor.txt");
+        fs::write(&path, "20C\n")?;
+
+        let mut tail = Tail::new(&path);
+        assert!(tail.poll()?.is_empty());
+
+        let mut file = OpenOptions::new().append(true).open(&path)?;
+        write!(file, "21C\n22")?;
+        assert_eq!(tail.poll()?, ["21C"]);
+        writeln!(file, "C\r")?;
+        assert_eq!(tail.poll()?, ["22C"]);
+
+        fs::write(&path, "5C\n")?;
+        assert_eq!(tail.poll()?, ["5C"]);
+        fs::remove_file(&path)?;
+        assert!(tail.poll()?.is_empty());
+        fs::write(&path, "6C\n")?;
+        assert_eq!(tail.poll()?, ["6C"]);
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
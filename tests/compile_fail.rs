@@ -0,0 +1,7 @@
+//! Combinations of temperatures and temperature differences that shouldn't compile, since they
+//! mix up the two quantities.
+
+#[test]
+fn test_temp_and_delta_dont_mix() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
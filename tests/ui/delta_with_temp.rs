@@ -0,0 +1,9 @@
+use cfk::temp::{Temp, TempDelta, TempUnit};
+use rust_decimal::Decimal;
+
+fn main() {
+    let temp = Temp::from_base_unit(Decimal::TEN, TempUnit::KELVIN);
+    let delta = TempDelta::of(Decimal::TEN, TempUnit::KELVIN);
+    let _ = delta + temp;
+    let _ = delta - temp;
+}
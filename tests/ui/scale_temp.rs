@@ -0,0 +1,7 @@
+use cfk::temp::{Temp, TempUnit};
+use rust_decimal::Decimal;
+
+fn main() {
+    let temp = Temp::from_base_unit(Decimal::TEN, TempUnit::KELVIN);
+    let _ = temp * Decimal::TWO;
+}